@@ -0,0 +1,49 @@
+use std::{fs, hint::black_box};
+
+use aoc_202x::DAYS;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+// Benchmarks parsing, preparing and each part of every day with an input in
+// inputs/. Days without one, and parts that fail (like unimplemented stubs), are
+// skipped.
+// Run a single day with `cargo bench -- day04`.
+fn bench_days(c: &mut Criterion) {
+    for &(day, solution) in DAYS {
+        let path = format!("inputs/day{:02}.txt", day);
+        let Ok(input) = fs::read_to_string(&path) else {
+            eprintln!("Skipping day {}: no input at {}", day, path);
+            continue;
+        };
+        let parsed = match solution.parse_input(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("Skipping day {}: {}", day, e);
+                continue;
+            }
+        };
+        let mut group = c.benchmark_group(format!("day{:02}", day));
+        group.bench_function("parse", |b| {
+            b.iter(|| solution.parse_only(black_box(input.as_bytes())))
+        });
+        group.bench_function("prepare", |b| {
+            b.iter_batched(
+                || solution.parse_unprepared(&input).unwrap(),
+                |mut parsed| solution.prepare_input(black_box(&mut parsed)),
+                BatchSize::SmallInput,
+            )
+        });
+        for part in 1..=2 {
+            if let Err(e) = solution.solve_part(&parsed, part).answer {
+                eprintln!("Skipping day {} part {}: {}", day, part, e);
+                continue;
+            }
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| solution.solve_part(black_box(&parsed), part))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+use std::{fmt::Display, fs};
+
+// Known-correct answers for a day, one line per part (blank while unknown)
+#[derive(Debug, Default)]
+pub struct Answers {
+    parts: [Option<String>; 2],
+}
+
+pub enum Check<'a> {
+    Correct,
+    Wrong { expected: &'a str },
+    Unverified,
+}
+
+impl Display for Check<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Correct => write!(f, " (correct)"),
+            Check::Wrong { expected } => write!(f, " (wrong, expected {})", expected),
+            Check::Unverified => Ok(()),
+        }
+    }
+}
+
+pub fn answers_path(day: usize, example: bool) -> String {
+    match example {
+        true => format!("inputs/day{:02}.example.answers", day),
+        false => format!("inputs/day{:02}.answers", day),
+    }
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Answers {
+        let mut answers = Answers::default();
+        if let Ok(contents) = fs::read_to_string(path) {
+            for (slot, line) in answers.parts.iter_mut().zip(contents.lines()) {
+                if !line.trim().is_empty() {
+                    *slot = Some(line.trim().to_string());
+                }
+            }
+        }
+        answers
+    }
+
+    pub fn save(&self, path: &str) {
+        let contents: Vec<&str> = self
+            .parts
+            .iter()
+            .map(|part| part.as_deref().unwrap_or(""))
+            .collect();
+        fs::write(path, contents.join("\n") + "\n").unwrap();
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.parts[part - 1].as_deref()
+    }
+
+    pub fn set(&mut self, part: usize, answer: &str) {
+        self.parts[part - 1] = Some(answer.to_string());
+    }
+
+    pub fn check(&self, part: usize, answer: &str) -> Check<'_> {
+        match self.get(part) {
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Wrong { expected },
+            None => Check::Unverified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answers, Check};
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        answers.set(2, "281");
+        assert!(matches!(answers.check(1, "142"), Check::Unverified));
+        assert!(matches!(answers.check(2, "281"), Check::Correct));
+        assert!(matches!(
+            answers.check(2, "280"),
+            Check::Wrong { expected: "281" }
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+// Puzzles unlock at midnight US Eastern, which is UTC-5 throughout December
+fn unlock_zone() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).unwrap()
+}
+
+pub fn unlock_time(year: usize, day: usize) -> DateTime<Utc> {
+    unlock_zone()
+        .with_ymd_and_hms(year as i32, 12, day as u32, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+// The day of the most recently unlocked puzzle, if `now` is during the event
+pub fn puzzle_day(now: DateTime<Utc>) -> Option<usize> {
+    let now = now.with_timezone(&unlock_zone());
+    if now.month() == 12 && (1..=25).contains(&now.day()) {
+        Some(now.day() as usize)
+    } else {
+        None
+    }
+}
+
+// The first puzzle of `year` that is still locked at `now`, with its unlock time
+pub fn next_unlock(year: usize, now: DateTime<Utc>) -> Option<(usize, DateTime<Utc>)> {
+    (1..=25)
+        .map(|day| (day, unlock_time(year, day)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::{next_unlock, puzzle_day};
+
+    #[test]
+    fn test_puzzle_day_uses_eastern_midnight() {
+        let before = Utc.with_ymd_and_hms(2023, 12, 1, 4, 59, 59).unwrap();
+        let after = Utc.with_ymd_and_hms(2023, 12, 1, 5, 0, 0).unwrap();
+        let last = Utc.with_ymd_and_hms(2023, 12, 26, 4, 59, 59).unwrap();
+        let over = Utc.with_ymd_and_hms(2023, 12, 26, 5, 0, 0).unwrap();
+        assert_eq!(puzzle_day(before), None);
+        assert_eq!(puzzle_day(after), Some(1));
+        assert_eq!(puzzle_day(last), Some(25));
+        assert_eq!(puzzle_day(over), None);
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        let now = Utc.with_ymd_and_hms(2023, 12, 6, 23, 0, 0).unwrap();
+        let (day, unlock) = next_unlock(2023, now).unwrap();
+        assert_eq!(day, 7);
+        assert_eq!(unlock, Utc.with_ymd_and_hms(2023, 12, 7, 5, 0, 0).unwrap());
+        assert_eq!(
+            next_unlock(2023, unlock),
+            Some((8, unlock + chrono::Duration::days(1)))
+        );
+        let over = Utc.with_ymd_and_hms(2023, 12, 25, 5, 0, 0).unwrap();
+        assert_eq!(next_unlock(2023, over), None);
+    }
+}
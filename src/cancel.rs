@@ -0,0 +1,37 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Set once the runner has given up on the current part, e.g. after `--timeout`.
+/// Long loops should check it every so often and return early; whatever they
+/// return is thrown away.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<CancellationToken> = RefCell::default();
+}
+
+// The token of the part running on this thread. Days that spawn their own
+// threads should clone it before doing so.
+pub fn cancellation() -> CancellationToken {
+    CURRENT.with_borrow(|token| token.clone())
+}
+
+pub(crate) fn set_current(token: CancellationToken) {
+    CURRENT.set(token)
+}
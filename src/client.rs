@@ -0,0 +1,74 @@
+use std::fs;
+
+use reqwest::blocking::{Client, Response};
+
+// Fetches a page of adventofcode.com, authenticated with the .session cookie
+pub fn get(path: &str) -> Response {
+    // Read session cookie from .session file
+    let session = fs::read_to_string(".session").expect("Could not find .session file");
+    let session = session.trim();
+    let url = format!("https://adventofcode.com/{}", path);
+    Client::new()
+        .get(url)
+        .header("cookie", format!("session={};", session))
+        .send()
+        .unwrap()
+}
+
+// Pulls the text of every `<article>` (one per unlocked part) out of a puzzle page
+pub fn puzzle_text(html: &str) -> String {
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        articles.push(strip_tags(&rest[start..start + end]));
+        rest = &rest[start + end..];
+    }
+    articles.join("\n\n")
+}
+
+// The text of `html` with tags dropped and the common entities decoded
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut tag: Option<String> = None;
+    for c in html.chars() {
+        match (c, &mut tag) {
+            ('<', None) => tag = Some(String::new()),
+            ('>', Some(name)) => {
+                // Keep paragraphs, headings and list items on their own lines
+                if ["/p", "/h2", "/li", "/pre"].contains(&name.as_str()) {
+                    text.push('\n');
+                }
+                tag = None
+            }
+            (c, Some(name)) => name.push(c),
+            (c, None) => text.push(c),
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::puzzle_text;
+
+    #[test]
+    fn test_puzzle_text() {
+        let html = "<main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>\
+            <p>Something is <em>wrong</em> &amp; the <code>a&lt;b</code>.</p></article>\
+            <p>Answer: <code>1</code></p>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2></article></main>";
+        assert_eq!(
+            puzzle_text(html),
+            "--- Day 1: Trebuchet?! ---\nSomething is wrong & the a<b.\n\n--- Part Two ---"
+        );
+    }
+}
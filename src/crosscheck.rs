@@ -0,0 +1,68 @@
+use std::fs;
+
+use aoc_202x::find_day;
+
+use crate::answers::{answers_path, Answers};
+use crate::{example_path, input_path};
+
+// Solves each part of `day` with every one of its variants, all on the same
+// parsed input, and reports the answers and how long each took next to the
+// default. Runs on this thread without a timeout, so slow variants are best
+// checked against the example. Returns whether every variant agreed.
+pub fn crosscheck(day: usize, example: bool) -> bool {
+    let fp = match example {
+        true => example_path(day),
+        false => input_path(day),
+    };
+    let solver = find_day(day).unwrap_or_else(|e| panic!("Could not check day {}: {}", day, e));
+    let contents =
+        fs::read_to_string(&fp).unwrap_or_else(|e| panic!("Could not read {}: {}", fp, e));
+    let answers = Answers::load(&answers_path(day, example));
+    println!("======== DAY {} ========", day);
+    let input = match solver.parse_input(&contents) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("Unable to parse file: {}", e);
+            return false;
+        }
+    };
+    let mut agreed = true;
+    for part in 1..=2 {
+        let results: Vec<_> = solver
+            .variants(part)
+            .into_iter()
+            .map(|variant| (variant, solver.solve_variant(&input, part, variant)))
+            .collect();
+        let default_elapsed = results[0].1.elapsed;
+        for (variant, result) in &results {
+            let answer = match &result.answer {
+                Ok(answer) => format!("{}{}", answer, answers.check(part, answer)),
+                Err(e) => format!("failed: {}", e),
+            };
+            let relative = match default_elapsed.is_zero() {
+                true => String::new(),
+                false => format!(
+                    "{:.2}x",
+                    result.elapsed.as_secs_f64() / default_elapsed.as_secs_f64()
+                ),
+            };
+            println!(
+                "Part {}  {:<12} {:>12.3}ms {:>9}  {}",
+                part,
+                variant,
+                result.elapsed.as_secs_f64() * 1e3,
+                relative,
+                answer
+            );
+        }
+        let (first, rest) = results.split_first().unwrap();
+        if rest
+            .iter()
+            .any(|(_, result)| result.answer.as_ref().ok() != first.1.answer.as_ref().ok())
+        {
+            println!("Part {}'s variants disagree", part);
+            agreed = false;
+        }
+    }
+    agreed
+}
@@ -1,50 +1,49 @@
 use nom::{
-    character::{
-        complete::{alphanumeric1, line_ending},
-    },
-    combinator::map,
+    character::complete::{alphanumeric1, line_ending},
+    combinator::all_consuming,
     multi::separated_list1,
     IResult,
 };
 
 use crate::days::Day;
+use crate::parser::MyErr;
 
 pub struct Day01;
 
 impl Day01 {
-    fn first_last_digits(input: &str) -> (u32, u32) {
+    fn first_last_digits(input: &[u8]) -> (u32, u32) {
         let mut output: (Option<u32>, u32) = (None, 0);
-        input.chars().for_each(|c| {
-            if c.is_numeric() {
-                let digit = c.to_digit(10);
+        input.iter().for_each(|c| {
+            if c.is_ascii_digit() {
+                let digit = (c - b'0') as u32;
                 if output.0.is_none() {
-                    output.0 = digit;
+                    output.0 = Some(digit);
                 }
-                output.1 = digit.unwrap()
+                output.1 = digit
             }
         });
         (output.0.unwrap(), output.1)
     }
 
-    fn first_last_digits_words(input: &str) -> (u32, u32) {
+    fn first_last_digits_words(input: &[u8]) -> (u32, u32) {
         let mut output: (Option<u32>, u32) = (None, 0);
-        input.chars().enumerate().for_each(|(pos, c)| {
-            if c.is_numeric() {
-                let digit = c.to_digit(10);
+        input.iter().enumerate().for_each(|(pos, c)| {
+            if c.is_ascii_digit() {
+                let digit = (c - b'0') as u32;
                 if output.0.is_none() {
-                    output.0 = digit;
+                    output.0 = Some(digit);
                 }
-                output.1 = digit.unwrap()
+                output.1 = digit
             } else if let Some(number) = match &input[pos..] {
-                s if s.starts_with("one") => Some(1),
-                s if s.starts_with("two") => Some(2),
-                s if s.starts_with("three") => Some(3),
-                s if s.starts_with("four") => Some(4),
-                s if s.starts_with("five") => Some(5),
-                s if s.starts_with("six") => Some(6),
-                s if s.starts_with("seven") => Some(7),
-                s if s.starts_with("eight") => Some(8),
-                s if s.starts_with("nine") => Some(9),
+                s if s.starts_with(b"one") => Some(1),
+                s if s.starts_with(b"two") => Some(2),
+                s if s.starts_with(b"three") => Some(3),
+                s if s.starts_with(b"four") => Some(4),
+                s if s.starts_with(b"five") => Some(5),
+                s if s.starts_with(b"six") => Some(6),
+                s if s.starts_with(b"seven") => Some(7),
+                s if s.starts_with(b"eight") => Some(8),
+                s if s.starts_with(b"nine") => Some(9),
                 _ => None,
             } {
                 if output.0.is_none() {
@@ -56,21 +55,27 @@ impl Day01 {
         (output.0.unwrap(), output.1)
     }
 
+    fn parse_lines(input: &[u8]) -> IResult<&[u8], Vec<&[u8]>> {
+        separated_list1(line_ending, alphanumeric1)(input)
+    }
+
     fn combine_nums(input: (u32, u32)) -> u32 {
         input.0 * 10 + input.1
     }
 }
 
 impl Day for Day01 {
-    type Input = Vec<String>;
+    // Lines borrowed from the input, no copies
+    type Input<'a> = Vec<&'a [u8]>;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        separated_list1(line_ending, map(alphanumeric1, |s: &str| s.to_string()))(input)
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, MyErr> {
+        let (_, lines) = all_consuming(Self::parse_lines)(input)?;
+        Ok(lines)
     }
 
     type Output1 = u32;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
         input
             .iter()
             .map(|s| Self::first_last_digits(s))
@@ -80,7 +85,7 @@ impl Day for Day01 {
 
     type Output2 = u32;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
         input
             .iter()
             .map(|s| Self::first_last_digits_words(s))
@@ -9,7 +9,8 @@ use nom::{
     IResult,
 };
 
-use crate::days::Day;
+use crate::days::{parse_utf8, Day};
+use crate::parser::MyErr;
 
 pub struct Day02;
 
@@ -66,6 +67,10 @@ impl Day02 {
             },
         )(input)
     }
+
+    fn parse(input: &str) -> IResult<&str, Vec<Game>> {
+        separated_list1(newline, Self::single_game)(input)
+    }
 }
 
 #[derive(Debug)]
@@ -97,15 +102,15 @@ impl Game {
 }
 
 impl Day for Day02 {
-    type Input = Vec<Game>;
+    type Input<'a> = Vec<Game>;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        separated_list1(newline, Self::single_game)(input)
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, MyErr> {
+        parse_utf8(input, Self::parse)
     }
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
         input
             .iter()
             .filter(|p| p.possible_part1())
@@ -115,7 +120,7 @@ impl Day for Day02 {
 
     type Output2 = usize;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
         input
             .iter()
             .map(|s| s.minimum_cubes())
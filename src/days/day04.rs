@@ -1,7 +1,6 @@
-use std::{
-    ops::RangeInclusive,
-};
+use std::ops::RangeInclusive;
 
+use fastrand::Rng;
 use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, space1, u32},
@@ -11,13 +10,18 @@ use nom::{
     IResult,
 };
 
-use crate::days::Day;
+use crate::days::{parse_utf8, Day, Variants};
+use crate::generator::Generator;
+use crate::parser::MyErr;
 
 #[derive(Debug, Eq)]
 pub struct Card {
     index: u32,
     winners: Vec<u32>,
     contestants: Vec<u32>,
+    // Filled in by `Day04::prepare`, both parts need it. None until then, so parts
+    // given a card straight from the parser fail instead of scoring it as 0.
+    matches: Option<usize>,
 }
 
 impl PartialEq for Card {
@@ -46,21 +50,33 @@ impl Card {
             .count()
     }
 
-    fn score_p1(&self) -> u32 {
-        Self::score(self.num_matches())
+    fn matches(&self) -> Result<usize, String> {
+        self.matches
+            .ok_or_else(|| format!("card {} has not been prepared", self.index))
+    }
+
+    fn score_p1(&self) -> Result<u32, String> {
+        Ok(Self::score(self.matches()?))
     }
 
-    fn win_indexes(&self) -> Option<RangeInclusive<usize>> {
-        if self.num_matches() == 0 {
-            None
+    fn win_indexes(&self) -> Result<Option<RangeInclusive<usize>>, String> {
+        let matches = self.matches()?;
+        if matches == 0 {
+            Ok(None)
         } else {
-            Some((self.index as usize) + 1..=(self.index as usize + self.num_matches()))
+            Ok(Some(
+                (self.index as usize) + 1..=(self.index as usize + matches),
+            ))
         }
     }
 }
 
 pub struct Day04;
 
+// How many numbers generated cards have on each side, as in the example
+const WINNERS: usize = 5;
+const CONTESTANTS: usize = 8;
+
 impl Day04 {
     fn parse_single_card(input: &str) -> IResult<&str, Card> {
         map(
@@ -79,27 +95,52 @@ impl Day04 {
                 index,
                 winners,
                 contestants,
+                matches: None,
             },
         )(input)
     }
+
+    // Counts each card's copies in one pass, as cards only win copies of later ones
+    fn tally_copies(input: &[Card]) -> Result<usize, String> {
+        let mut copies = vec![1; input.len()];
+        for (position, card) in input.iter().enumerate() {
+            let count = copies[position];
+            for index in card.win_indexes()?.into_iter().flatten() {
+                *copies.get_mut(index - 1).ok_or_else(|| {
+                    format!("won a copy of card {}, which does not exist", index)
+                })? += count;
+            }
+        }
+        Ok(copies.iter().sum())
+    }
+
+    fn parse(input: &str) -> IResult<&str, Vec<Card>> {
+        separated_list1(line_ending, Self::parse_single_card)(input)
+    }
 }
 
 impl Day for Day04 {
-    type Input = Vec<Card>;
+    type Input<'a> = Vec<Card>;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        separated_list1(line_ending, Self::parse_single_card)(input)
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, MyErr> {
+        parse_utf8(input, Self::parse)
+    }
+
+    fn prepare(input: &mut Self::Input<'_>) {
+        for card in input {
+            card.matches = Some(card.num_matches());
+        }
     }
 
-    type Output1 = usize;
+    type Output1 = Result<usize, String>;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input.iter().map(|g| g.score_p1() as usize).sum()
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
+        input.iter().map(|g| Ok(g.score_p1()? as usize)).sum()
     }
 
-    type Output2 = usize;
+    type Output2 = Result<usize, String>;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
         let mut cards_won = input.len();
         let mut cards_to_check: Vec<usize> = Default::default();
         // push all the cards in first
@@ -108,8 +149,10 @@ impl Day for Day04 {
         }
         // println!("array: {:?}", cards_to_check);
         while let Some(next_idx) = cards_to_check.pop() {
-            let card = input.get(next_idx - 1).unwrap();
-            if let Some(wins) = card.win_indexes() {
+            let card = input
+                .get(next_idx - 1)
+                .ok_or_else(|| format!("won a copy of card {}, which does not exist", next_idx))?;
+            if let Some(wins) = card.win_indexes()? {
                 // let score = card.num_matches();
                 // println!(
                 //     "Card {} wins {} cards, adding {:?}",
@@ -124,6 +167,46 @@ impl Day for Day04 {
             // println!("array: {:?}", cards_to_check);
             cards_to_check.sort()
         }
-        cards_won
+        Ok(cards_won)
+    }
+
+    fn variants_2() -> Variants<Self, Self::Output2> {
+        &[("tally", |input| Self::tally_copies(input))]
+    }
+}
+
+impl Generator for Day04 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let cards = size.max(1);
+        let join = |numbers: &[u32]| {
+            let numbers: Vec<String> = numbers.iter().map(|n| format!("{:>2}", n)).collect();
+            numbers.join(" ")
+        };
+        (1..=cards)
+            .map(|index| {
+                let mut numbers: Vec<u32> = (1..100).collect();
+                rng.shuffle(&mut numbers);
+                let (winners, others) = numbers.split_at(WINNERS);
+                // Mostly losing cards, so the copies don't multiply out of hand, and
+                // never winning copies of cards past the end
+                let matches = match rng.usize(0..4) {
+                    0 => rng.usize(0..=WINNERS.min(cards - index)),
+                    _ => 0,
+                };
+                let mut contestants: Vec<u32> = winners[..matches]
+                    .iter()
+                    .chain(&others[..CONTESTANTS - matches])
+                    .copied()
+                    .collect();
+                rng.shuffle(&mut contestants);
+                format!(
+                    "Card {:>3}: {} | {}",
+                    index,
+                    join(winners),
+                    join(&contestants)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
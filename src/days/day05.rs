@@ -1,6 +1,7 @@
 use std::{fmt::Display, ops::Range};
 
-use itertools::Itertools;
+use fastrand::Rng;
+use log::debug;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,11 +12,31 @@ use nom::{
     IResult,
 };
 
-use crate::days::Day;
+use crate::cancel::cancellation;
+use crate::days::{parse_utf8, Day, Variants};
+use crate::generator::Generator;
+use crate::parser::MyErr;
+use crate::progress::progress;
+
+// How many seeds to map between progress updates and cancellation checks
+const PROGRESS_BATCH: u64 = 1 << 16;
 
 pub struct Day05;
 
-#[derive(Debug)]
+// Generated numbers stay below this times the size, so brute force keeps up
+const GENERATED_SPAN: u64 = 20;
+const THINGS: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+#[derive(Debug, PartialEq)]
 pub enum Thing {
     Seed,
     Soil,
@@ -33,58 +54,82 @@ pub struct Almanac {
     maps: Vec<Map>,
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Eq)]
-struct Roundtrip {
-    start_seed: u64,
-    end_dest: u64,
-}
-
-impl Ord for Roundtrip {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.end_dest.cmp(&other.end_dest)
-    }
-}
-
 impl Almanac {
     fn map_all_seeds_p1(&self) -> u64 {
-        let mut lowest_seen = (u64::MAX, u64::MAX);
+        let mut lowest_seen = u64::MAX;
         for seed in &self.seed_input {
             let mut cur_number = *seed;
             for map in &self.maps {
                 cur_number = map.map(cur_number);
             }
-            if cur_number < lowest_seen.0 {
-                lowest_seen = (cur_number, *seed);
-            }
+            lowest_seen = lowest_seen.min(cur_number);
         }
-        lowest_seen.1
+        lowest_seen
     }
 
-    fn map_all_seeds_p2(&self) -> u64 {
-        let mut lowest_seen = (u64::MAX, u64::MAX);
+    fn map_all_seeds_p2(&self) -> Result<u64, String> {
+        let mut lowest_seen = u64::MAX;
         let chunks = self.seed_input.chunks(2);
-        let mut index = 1;
-        for chunk in chunks {
+        let progress = progress();
+        let cancellation = cancellation();
+        progress.set_total(chunks.clone().map(|chunk| chunk[1]).sum());
+        for (index, chunk) in (1..).zip(chunks) {
             let seed_start = chunk[0];
             let seed_num = chunk[1];
-            println!(
+            debug!(
                 "starting seed run {} of {} - {} iters",
                 index,
                 self.seed_input.len() / 2,
                 seed_num
             );
+            progress.set_message(format!(
+                "seeds in run {} of {}",
+                index,
+                self.seed_input.len() / 2
+            ));
             for seed in seed_start..(seed_num + seed_start) {
                 let mut cur_number = seed;
                 for map in &self.maps {
                     cur_number = map.map(cur_number);
                 }
-                if cur_number < lowest_seen.0 {
-                    lowest_seen = (cur_number, seed)
+                lowest_seen = lowest_seen.min(cur_number);
+                if (seed - seed_start) % PROGRESS_BATCH == PROGRESS_BATCH - 1 {
+                    progress.inc(PROGRESS_BATCH);
+                    if cancellation.is_cancelled() {
+                        return Err("cancelled".to_string());
+                    }
                 }
             }
-            index += 1
+            progress.inc(seed_num % PROGRESS_BATCH);
         }
-        lowest_seen.1
+        Ok(lowest_seen)
+    }
+
+    // Maps whole ranges of seeds at once, splitting them wherever a map's ranges
+    // begin or end, rather than one seed at a time
+    fn lowest_location(&self, seeds: Vec<Range<u64>>) -> u64 {
+        let mut ranges = seeds;
+        for map in &self.maps {
+            ranges = ranges
+                .into_iter()
+                .flat_map(|range| map.map_range(range))
+                .collect();
+        }
+        ranges
+            .iter()
+            .map(|range| range.start)
+            .min()
+            .unwrap_or(u64::MAX)
+    }
+
+    fn lowest_location_p1(&self) -> u64 {
+        let seeds = self.seed_input.iter().map(|seed| *seed..seed + 1);
+        self.lowest_location(seeds.collect())
+    }
+
+    fn lowest_location_p2(&self) -> u64 {
+        let seeds = self.seed_input.chunks(2);
+        self.lowest_location(seeds.map(|chunk| chunk[0]..chunk[0] + chunk[1]).collect())
     }
 }
 
@@ -119,39 +164,46 @@ impl Map {
         }
         input
     }
+
+    fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut mapped = Vec::new();
+        let mut unmapped = vec![range];
+        for map_range in &self.ranges {
+            let source = map_range.to_input_range();
+            let mut rest = Vec::new();
+            for range in unmapped {
+                let start = range.start.max(source.start);
+                let end = range.end.min(source.end);
+                if start >= end {
+                    rest.push(range);
+                    continue;
+                }
+                let offset = map_range.dest_start;
+                mapped.push(start - source.start + offset..end - source.start + offset);
+                if range.start < start {
+                    rest.push(range.start..start);
+                }
+                if end < range.end {
+                    rest.push(end..range.end);
+                }
+            }
+            unmapped = rest;
+        }
+        mapped.extend(unmapped);
+        mapped
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 struct MapRange {
     dest_start: u64,
     source_start: u64,
     range_length: u64,
 }
 
-mod test {
-    use super::MapRange;
-
-    #[test]
-    fn test_ordering() {
-        let res_a = MapRange {
-            dest_start: 10,
-            source_start: 1000,
-            range_length: 0,
-        };
-        let res_b = MapRange {
-            dest_start: 20,
-            source_start: 0,
-            range_length: 0,
-        };
-        assert!(res_b > res_a);
-        assert!(res_a < res_b);
-        let res_c: Option<MapRange> = None;
-        let res_d = MapRange {
-            dest_start: 10,
-            source_start: 0,
-            range_length: 0,
-        };
-        assert!(res_c < Some(res_d));
+impl PartialOrd for MapRange {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -235,12 +287,8 @@ impl Day05 {
             },
         )(input)
     }
-}
-
-impl Day for Day05 {
-    type Input = Almanac;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
+    fn parse(input: &str) -> IResult<&str, Almanac> {
         map(
             tuple((
                 Self::parse_init_seeds,
@@ -253,16 +301,186 @@ impl Day for Day05 {
             },
         )(input)
     }
+}
+
+impl Day for Day05 {
+    type Input<'a> = Almanac;
+
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, MyErr> {
+        parse_utf8(input, Self::parse)
+    }
+
+    // Both parts map each number through the first map that covers it, and the
+    // interval variants split ranges, so none of a map's ranges may overlap
+    fn validate(input: &Self::Input<'_>) -> Result<(), Vec<String>> {
+        let mut violations = Vec::new();
+        if input.seed_input.len() % 2 != 0 {
+            violations.push(format!(
+                "there are {} seed numbers, which don't pair up into ranges",
+                input.seed_input.len()
+            ));
+        }
+        let mut expected = &Thing::Seed;
+        for map in &input.maps {
+            if map.src_type != *expected {
+                violations.push(format!("{} should map from {:?}", map, expected));
+            }
+            expected = &map.dest_type;
+        }
+        if *expected != Thing::Location {
+            violations.push(format!("the maps end at {:?}, not Location", expected));
+        }
+        for map in &input.maps {
+            let mut sources: Vec<_> = map.ranges.iter().map(MapRange::to_input_range).collect();
+            sources.sort_by_key(|source| source.start);
+            for pair in sources.windows(2) {
+                if pair[0].end > pair[1].start {
+                    violations.push(format!(
+                        "{} has overlapping ranges {:?} and {:?}",
+                        map, pair[0], pair[1]
+                    ));
+                }
+            }
+        }
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
 
     type Output1 = u64;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
         input.map_all_seeds_p1()
     }
 
-    type Output2 = u64;
+    type Output2 = Result<u64, String>;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
         input.map_all_seeds_p2()
     }
+
+    fn variants_1() -> Variants<Self, Self::Output1> {
+        &[("intervals", |input| input.lowest_location_p1())]
+    }
+
+    fn variants_2() -> Variants<Self, Self::Output2> {
+        &[("intervals", |input| Ok(input.lowest_location_p2()))]
+    }
+}
+
+impl Generator for Day05 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let span = GENERATED_SPAN * size as u64;
+        let mut seeds = Vec::new();
+        for _ in 0..size {
+            seeds.push(rng.u64(0..span).to_string());
+            seeds.push(rng.u64(1..=span / 4).to_string());
+        }
+        let mut maps = Vec::new();
+        for things in THINGS.windows(2) {
+            // Cut 0..span into pieces and send some of them somewhere else, so
+            // no two ranges overlap
+            let mut cuts: Vec<u64> = (0..rng.usize(1..=size)).map(|_| rng.u64(0..span)).collect();
+            cuts.extend([0, span]);
+            cuts.sort_unstable();
+            cuts.dedup();
+            let mut ranges = Vec::new();
+            for piece in cuts.windows(2) {
+                if ranges.is_empty() || rng.bool() {
+                    let dest = rng.u64(0..span);
+                    ranges.push(format!("{} {} {}", dest, piece[0], piece[1] - piece[0]));
+                }
+            }
+            rng.shuffle(&mut ranges);
+            maps.push(format!(
+                "{}-to-{} map:\n{}",
+                things[0],
+                things[1],
+                ranges.join("\n")
+            ));
+        }
+        format!("seeds: {}\n\n{}", seeds.join(" "), maps.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Day05, MapRange};
+    use crate::days::Day;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_example() {
+        let almanac = Day05::parse_str(EXAMPLE).unwrap();
+        assert_eq!(almanac.map_all_seeds_p1(), 35);
+        assert_eq!(almanac.lowest_location_p1(), 35);
+        assert_eq!(almanac.map_all_seeds_p2(), Ok(46));
+        assert_eq!(almanac.lowest_location_p2(), 46);
+        assert_eq!(Day05::validate(&almanac), Ok(()));
+
+        let overlapping = EXAMPLE.replace("52 50 48", "52 50 49");
+        let violations = Day05::validate(&Day05::parse_str(&overlapping).unwrap()).unwrap_err();
+        assert_eq!(
+            violations,
+            ["[Seed]->[Soil](2 maps) has overlapping ranges 50..99 and 98..100"]
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let res_a = MapRange {
+            dest_start: 10,
+            source_start: 1000,
+            range_length: 0,
+        };
+        let res_b = MapRange {
+            dest_start: 20,
+            source_start: 0,
+            range_length: 0,
+        };
+        assert!(res_b > res_a);
+        assert!(res_a < res_b);
+        let res_c: Option<MapRange> = None;
+        let res_d = MapRange {
+            dest_start: 10,
+            source_start: 0,
+            range_length: 0,
+        };
+        assert!(res_c < Some(res_d));
+    }
 }
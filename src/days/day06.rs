@@ -1,3 +1,5 @@
+use fastrand::Rng;
+use log::debug;
 use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, space1, u64},
@@ -7,10 +9,31 @@ use nom::{
     IResult,
 };
 
-use crate::days::Day;
+use crate::days::{parse_utf8, Day, Variants};
+use crate::generator::Generator;
+use crate::parser::MyErr;
 
 pub struct Day06;
 
+// The sheet as written, one race per column
+#[derive(Debug)]
+pub struct Sheet {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+}
+
+impl Sheet {
+    fn races(&self) -> impl Iterator<Item = Race> + '_ {
+        self.times
+            .iter()
+            .zip(&self.distances)
+            .map(|(time_ms, record_distance_mm)| Race {
+                time_ms: *time_ms,
+                record_distance_mm: *record_distance_mm,
+            })
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Race {
     time_ms: u64,
@@ -21,46 +44,75 @@ impl Race {
     fn travel_distance(&self, hold_time_ms: u64) -> u64 {
         match hold_time_ms {
             press_time if press_time >= self.time_ms => 0, // held beyond/until end of race
-            press_time if press_time == 0 => 0,            // not pressed, no travel
+            0 => 0,                                        // not pressed, no travel
             speed => {
                 let travel_time = self.time_ms - hold_time_ms;
                 speed * travel_time
             }
         }
     }
-}
 
-mod test {
-    use super::Race;
+    fn ways_to_win(&self) -> usize {
+        (1..self.time_ms)
+            .map(|press_time| self.travel_distance(press_time))
+            .filter(|distance| distance > &self.record_distance_mm)
+            .count()
+    }
 
-    #[test]
-    fn test_hold_time_example() {
-        let race1 = Race {
-            time_ms: 7,
-            record_distance_mm: 9,
-        };
-        assert_eq!(race1.travel_distance(0), 0);
-        assert_eq!(race1.travel_distance(1), 6);
-        assert_eq!(race1.travel_distance(2), 10);
-        assert_eq!(race1.travel_distance(3), 12);
-        assert_eq!(race1.travel_distance(4), 12);
-        assert_eq!(race1.travel_distance(5), 10);
-        assert_eq!(race1.travel_distance(6), 6);
-        assert_eq!(race1.travel_distance(7), 0);
-        assert_eq!(race1.travel_distance(u64::MAX), 0);
+    // The winning hold times are those between the roots of
+    // hold * (time - hold) = record, symmetric around half the race
+    fn ways_to_win_quadratic(&self) -> usize {
+        let time = self.time_ms as f64;
+        let discriminant = time * time - 4.0 * self.record_distance_mm as f64;
+        if discriminant < 0.0 {
+            return 0;
+        }
+        let mut shortest = ((time - discriminant.sqrt()) / 2.0).floor().max(0.0) as u64;
+        // Nudge past any floating point error onto the shortest winning hold
+        while shortest <= self.time_ms / 2
+            && self.travel_distance(shortest) <= self.record_distance_mm
+        {
+            shortest += 1;
+        }
+        while shortest > 0 && self.travel_distance(shortest - 1) > self.record_distance_mm {
+            shortest -= 1;
+        }
+        match shortest > self.time_ms / 2 {
+            true => 0,
+            false => (self.time_ms + 1 - 2 * shortest) as usize,
+        }
     }
 }
 
-fn combine_digits(lhs: u64, rhs: u64) -> Option<u64> {
+fn combine_digits(lhs: u64, rhs: u64) -> Result<u64, String> {
     let xstr = format!("{lhs}{rhs}");
-    println!("{lhs}+{rhs} combined to {xstr}");
-    xstr.parse().ok()
+    debug!("{lhs}+{rhs} combined to {xstr}");
+    xstr.parse()
+        .map_err(|e| format!("{lhs} and {rhs} combined to {xstr}, which is not a u64: {e}"))
 }
 
-impl Day for Day06 {
-    type Input = Vec<Race>;
+// Part 2's one long race, read from the sheet with the spaces taken out
+fn combined_race(sheet: &Sheet) -> Result<Race, String> {
+    let races: Vec<Race> = sheet.races().collect();
+    let (first, rest) = races.split_first().ok_or("there are no races")?;
+    let race = rest.iter().try_fold(
+        Race {
+            time_ms: first.time_ms,
+            record_distance_mm: first.record_distance_mm,
+        },
+        |acc, r| {
+            Ok::<Race, String>(Race {
+                record_distance_mm: combine_digits(acc.record_distance_mm, r.record_distance_mm)?,
+                time_ms: combine_digits(acc.time_ms, r.time_ms)?,
+            })
+        },
+    )?;
+    debug!("{race:?}");
+    Ok(race)
+}
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
+impl Day06 {
+    fn parse(input: &str) -> IResult<&str, Sheet> {
         map(
             tuple((
                 tag("Time:"),
@@ -71,60 +123,127 @@ impl Day for Day06 {
                 space1,
                 separated_list1(space1, u64),
             )),
-            |(_, _, times, _, _, _, distances)| {
-                let t: Vec<Race> = times
-                    .iter()
-                    .enumerate()
-                    .map(|t| Race {
-                        time_ms: *t.1,
-                        record_distance_mm: *distances.get(t.0).unwrap(),
-                    })
-                    .collect();
-                t
-            },
+            |(_, _, times, _, _, _, distances)| Sheet { times, distances },
         )(input)
     }
+}
+
+impl Day for Day06 {
+    type Input<'a> = Sheet;
+
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, MyErr> {
+        parse_utf8(input, Self::parse)
+    }
+
+    // A time without a distance (or the other way round) would be dropped
+    fn validate(input: &Self::Input<'_>) -> Result<(), Vec<String>> {
+        match input.times.len() == input.distances.len() {
+            true => Ok(()),
+            false => Err(vec![format!(
+                "there are {} times but {} distances",
+                input.times.len(),
+                input.distances.len()
+            )]),
+        }
+    }
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        let race_ways_to_win = input.iter().map(|race| {
-            (1..race.time_ms)
-                .map(|press_time| race.travel_distance(press_time))
-                .filter(|distance| distance > &race.record_distance_mm)
-                .count()
-        });
-        race_ways_to_win.fold(1, |acc, x| acc * x)
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
+        input.races().map(|race| race.ways_to_win()).product()
     }
 
-    type Output2 = usize;
+    type Output2 = Result<usize, String>;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        let p2_race = input
-            .iter()
-            .fold(None, |acc: Option<Race>, r| {
-                if let Some(acc) = acc {
-                    Some(Race {
-                        record_distance_mm: combine_digits(
-                            acc.record_distance_mm,
-                            r.record_distance_mm,
-                        )
-                        .unwrap(),
-                        time_ms: combine_digits(acc.time_ms, r.time_ms).unwrap(),
-                    })
-                } else {
-                    Some(Race {
-                        time_ms: r.time_ms,
-                        record_distance_mm: r.record_distance_mm,
-                    })
-                }
-            })
-            .unwrap();
-        println!("{p2_race:?}");
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
+        Ok(combined_race(input)?.ways_to_win())
+    }
 
-        (1..p2_race.time_ms)
-            .map(|press_time| p2_race.travel_distance(press_time))
-            .filter(|distance| distance > &p2_race.record_distance_mm)
-            .count()
+    fn variants_1() -> Variants<Self, Self::Output1> {
+        &[("quadratic", |input| {
+            input
+                .races()
+                .map(|race| race.ways_to_win_quadratic())
+                .product()
+        })]
+    }
+
+    fn variants_2() -> Variants<Self, Self::Output2> {
+        &[("quadratic", |input| {
+            Ok(combined_race(input)?.ways_to_win_quadratic())
+        })]
+    }
+}
+
+impl Generator for Day06 {
+    // Up to three races of at most two digit times, as part 2 runs their digits
+    // together into one race for brute force to count through
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let longest = (size as u64 * 10).clamp(1, 99);
+        let (mut times, mut distances) = (Vec::new(), Vec::new());
+        for _ in 0..rng.usize(1..=3) {
+            let time = rng.u64(1..=longest);
+            // The record is some hold time's distance, so it can be beaten or not
+            let hold = rng.u64(0..=time);
+            times.push(format!("{:>5}", time));
+            distances.push(format!("{:>5}", hold * (time - hold)));
+        }
+        format!(
+            "Time:    {}\nDistance:{}",
+            times.concat(),
+            distances.concat()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Day06, Race};
+    use crate::days::Day;
+
+    #[test]
+    fn test_hold_time_example() {
+        let race1 = Race {
+            time_ms: 7,
+            record_distance_mm: 9,
+        };
+        assert_eq!(race1.travel_distance(0), 0);
+        assert_eq!(race1.travel_distance(1), 6);
+        assert_eq!(race1.travel_distance(2), 10);
+        assert_eq!(race1.travel_distance(3), 12);
+        assert_eq!(race1.travel_distance(4), 12);
+        assert_eq!(race1.travel_distance(5), 10);
+        assert_eq!(race1.travel_distance(6), 6);
+        assert_eq!(race1.travel_distance(7), 0);
+        assert_eq!(race1.travel_distance(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_ways_to_win() {
+        for (time_ms, record_distance_mm, ways) in [
+            (7, 9, 4),
+            (15, 40, 8),
+            (30, 200, 9),
+            (71530, 940200, 71503),
+            (4, 4, 0),
+        ] {
+            let race = Race {
+                time_ms,
+                record_distance_mm,
+            };
+            assert_eq!(race.ways_to_win(), ways);
+            assert_eq!(race.ways_to_win_quadratic(), ways);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let sheet = Day06::parse_str("Time:      7  15   30\nDistance:  9  40  200").unwrap();
+        assert_eq!(Day06::validate(&sheet), Ok(()));
+        let sheet = Day06::parse_str("Time:      7  15   30\nDistance:  9  40").unwrap();
+        assert_eq!(
+            Day06::validate(&sheet),
+            Err(vec!["there are 3 times but 2 distances".to_string()])
+        );
     }
 }
@@ -1,25 +1,24 @@
-use nom::IResult;
-
 use crate::days::Day;
+use crate::parser::MyErr;
 
 pub struct Day07;
 
 impl Day for Day07 {
-    type Input = String;
+    type Input<'a> = String;
 
-    fn parse(_input: &str) -> IResult<&str, Self::Input> {
+    fn parse_bytes(_input: &[u8]) -> Result<Self::Input<'_>, MyErr> {
         unimplemented!("parser")
     }
 
     type Output1 = usize;
 
-    fn part_1(_input: &Self::Input) -> Self::Output1 {
+    fn part_1(_input: &Self::Input<'_>) -> Self::Output1 {
         unimplemented!("part_1")
     }
 
     type Output2 = usize;
 
-    fn part_2(_input: &Self::Input) -> Self::Output2 {
+    fn part_2(_input: &Self::Input<'_>) -> Self::Output2 {
         unimplemented!("part_2")
     }
 }
@@ -1,8 +1,18 @@
+use crate::alloc::{AllocStats, Measurement};
+use crate::cancel::{self, CancellationToken};
 use crate::parser::MyErr;
-use crate::Instant;
-use nom::{combinator::all_consuming, IResult};
+use crate::progress;
+use log::warn;
+use nom::{combinator::all_consuming, Parser};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
-use std::fs::read_to_string;
+use std::ops::Deref;
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub mod day01;
 pub mod day02;
@@ -30,47 +40,505 @@ pub mod day23;
 pub mod day24;
 pub mod day25;
 
-pub struct InputRest<T> {
+/// What a part can return: either a plain answer, or a `Result` when the part can fail.
+/// Plain answers are the integers, floats, `bool`, `char` and strings; wrap anything
+/// else that implements `Display` in [`Shown`].
+pub trait Answer {
+    fn into_answer(self) -> Result<String, MyErr>;
+}
+
+macro_rules! plain_answers {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn into_answer(self) -> Result<String, MyErr> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+plain_answers!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, String,
+    &str
+);
+
+/// An infallible answer shown through its `Display` impl, e.g. `Shown(grid)`
+#[derive(Debug)]
+pub struct Shown<T: Display>(pub T);
+
+impl<T: Display> Answer for Shown<T> {
+    fn into_answer(self) -> Result<String, MyErr> {
+        Ok(self.0.to_string())
+    }
+}
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, MyErr> {
+        self.map(|answer| answer.to_string())
+            .map_err(|e| MyErr::SolveError(e.to_string()))
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+// Runs `f`, turning a panic (such as an `unimplemented!` stub) into an error.
+// The panic is reported through the error instead of the usual panic message.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> Result<T, MyErr>) -> Result<T, MyErr> {
+    QUIET_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| match CATCHING.get() {
+            true => PANIC_LOCATION.set(info.location().map(|l| l.to_string())),
+            false => default_hook(info),
+        }));
+    });
+    CATCHING.set(true);
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(false);
+    result.unwrap_or_else(|payload| {
+        let message = match PANIC_LOCATION.take() {
+            Some(location) => format!("{} at {}", panic_message(payload), location),
+            None => panic_message(payload),
+        };
+        // `unimplemented!()` panics with exactly this, with or without a message
+        match message == "not implemented" || message.starts_with("not implemented: ") {
+            true => Err(MyErr::Unimplemented(message)),
+            false => Err(MyErr::Panic(message)),
+        }
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// What went wrong in a report, so it can be told apart without reading the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    // There is no input file to parse
+    NoInput,
+    // An `unimplemented!()` stub, e.g. in a freshly scaffolded day
+    Unimplemented,
+    Failed,
+}
+
+/// A failed phase of a run, with the error as the runner reported it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub message: String,
+}
+
+impl From<MyErr> for Failure {
+    fn from(e: MyErr) -> Failure {
+        let kind = match &e {
+            MyErr::FileError(e) if e.kind() == std::io::ErrorKind::NotFound => FailureKind::NoInput,
+            MyErr::Unimplemented(_) => FailureKind::Unimplemented,
+            _ => FailureKind::Failed,
+        };
+        Failure {
+            kind,
+            message: e.to_string(),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PartReport {
+    pub part: usize,
+    // Which of the day's variants solved the part, None for `part_1`/`part_2`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub answer: Result<String, Failure>,
+    pub elapsed: Duration,
+    pub alloc: Option<AllocStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayReport {
+    pub parse: Result<Duration, Failure>,
+    pub parse_alloc: Option<AllocStats>,
+    // None when parsing failed, so there was nothing to prepare, or with `check`
+    #[serde(default)]
+    pub prepare: Option<Result<Duration, Failure>>,
+    #[serde(default)]
+    pub prepare_alloc: Option<AllocStats>,
+    // The assumptions `Day::validate` found the input breaks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<String>,
+    pub parts: Vec<PartReport>,
+}
+
+// Parts run on their own thread, with plenty of stack for recursive solutions
+const WORKER_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+// The stack counts towards any address space limit (such as the one `run --isolate
+// --memory-limit` sets), so under one the worker only gets a quarter of it
+fn worker_stack_size() -> usize {
+    #[cfg(unix)]
+    {
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        // SAFETY: getrlimit only writes to the rlimit it is given
+        if unsafe { libc::getrlimit(libc::RLIMIT_AS, &mut limit) } == 0
+            && limit.rlim_cur != libc::RLIM_INFINITY
+        {
+            return WORKER_STACK_SIZE.min(limit.rlim_cur as usize / 4);
+        }
+    }
+    WORKER_STACK_SIZE
+}
+
+#[derive(Default)]
+pub struct RunConfig {
+    // Give up on a part (and cancel it) once it has run this long
+    pub timeout: Option<Duration>,
+    // Solve each part with this variant, for the parts that have one by that name
+    pub variant: Option<String>,
+    // Only parse and validate the input, without solving anything
+    pub check: bool,
+}
+
+/// Named alternatives to a day's `part_1` or `part_2` that output `O`
+pub type Variants<D, O> = &'static [(&'static str, fn(&<D as Day>::Input<'_>) -> O)];
+
+/// The name `part_1` and `part_2` go by among a day's variants
+pub const DEFAULT_VARIANT: &str = "default";
+
+fn find_variant<F: Copy>(variants: &[(&'static str, F)], name: &str) -> Option<F> {
+    variants
+        .iter()
+        .find(|(variant, _)| *variant == name)
+        .map(|(_, solve)| *solve)
+}
+
+// The variant `config` asks for if the part has it, else the default
+fn pick_variant<F>(variants: &[(&'static str, F)], config: &RunConfig) -> &'static str {
+    variants
+        .iter()
+        .map(|(name, _)| *name)
+        .find(|name| Some(*name) == config.variant.as_deref())
+        .unwrap_or(DEFAULT_VARIANT)
+}
+
+// Runs `solve` on a worker thread. It takes `input` by value, so a part given an
+// `Arc` drops it before reporting back.
+fn run_part<T: Send + 'static>(
+    part: usize,
+    variant: &'static str,
     input: T,
-    was_more: bool,
+    config: &RunConfig,
+    solve: fn(T, &str) -> Result<String, MyErr>,
+) -> PartReport {
+    let bar = progress::start(part);
+    let token = CancellationToken::default();
+    let (sender, receiver) = mpsc::channel();
+    let (worker_bar, worker_token) = (bar.clone(), token.clone());
+    let spawned = thread::Builder::new()
+        .name(format!("part {}", part))
+        .stack_size(worker_stack_size())
+        .spawn(move || {
+            progress::set_current(worker_bar);
+            cancel::set_current(worker_token);
+            let measurement = Measurement::start();
+            let before = Instant::now();
+            let answer = catch_panic(|| solve(input, variant));
+            let elapsed = before.elapsed();
+            // Nobody is listening any more if the part timed out
+            let _ = sender.send((answer, elapsed, measurement.finish()));
+        });
+    let (answer, elapsed, alloc) = match (spawned, config.timeout) {
+        // E.g. when there isn't enough memory left for the worker's stack
+        (Err(e), _) => (
+            Err(MyErr::SolveError(format!(
+                "could not start a worker thread: {}",
+                e
+            ))),
+            Duration::ZERO,
+            None,
+        ),
+        (Ok(_), None) => receiver.recv().unwrap(),
+        (Ok(_), Some(timeout)) => receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+            token.cancel();
+            (Err(MyErr::Timeout(timeout)), timeout, None)
+        }),
+    };
+    progress::finish(bar);
+    PartReport {
+        part,
+        variant: (variant != DEFAULT_VARIANT).then(|| variant.to_string()),
+        answer: answer.map_err(Failure::from),
+        elapsed,
+        alloc,
+    }
+}
+
+// What an input file is read into, so parsed inputs can borrow from it
+type Buffer = Box<dyn Deref<Target = [u8]> + Send + Sync>;
+
+#[cfg(not(feature = "mmap"))]
+fn read_input(fp: &str) -> std::io::Result<Buffer> {
+    Ok(Box::new(std::fs::read(fp)?))
+}
+
+// Maps the input into memory instead of reading it, with `--features mmap`. The
+// file must not be truncated or written to while it is mapped: a shrunk file makes
+// reads past its new end fault (SIGBUS), and parsed inputs would see their bytes
+// change under them.
+#[cfg(feature = "mmap")]
+fn read_input(fp: &str) -> std::io::Result<Buffer> {
+    let file = std::fs::File::open(fp)?;
+    // SAFETY: nothing writes to input files in place. `get-input` writes a new file
+    // and renames it over the old one, which leaves this mapping on the old file.
+    // Editing an input in place while a day runs breaks this, which is why mapping
+    // is opt-in.
+    Ok(Box::new(unsafe { memmap2::Mmap::map(&file) }?))
+}
+
+/// A day's parsed input together with the buffer it borrows from, so it can be
+/// handed to worker threads. The input is only ever lent to the day's own
+/// functions, which are generic over its lifetime and so can't hold on to it.
+pub(crate) struct Parsed<D: Day> {
+    // Declared before `_buffer`, so it is dropped first
+    input: D::Input<'static>,
+    _buffer: Buffer,
+}
+
+impl<D: Day + 'static> Parsed<D> {
+    pub(crate) fn parse(buffer: Buffer) -> Result<Parsed<D>, MyErr> {
+        // SAFETY: the bytes are on the heap (or mapped), so they don't move with
+        // `buffer`, and they outlive `input`, which is dropped first
+        let bytes: &'static [u8] = unsafe { &*(&**buffer as *const [u8]) };
+        Ok(Parsed {
+            input: D::parse_bytes(bytes)?,
+            _buffer: buffer,
+        })
+    }
+
+    fn read(fp: &str) -> Result<Parsed<D>, MyErr> {
+        Parsed::parse(read_input(fp)?)
+    }
+
+    pub(crate) fn prepare(&mut self) {
+        D::prepare(&mut self.input)
+    }
+
+    // A panicking check counts as a violation too
+    pub(crate) fn validate(&self) -> Vec<String> {
+        match catch_panic(|| Ok(D::validate(&self.input))) {
+            Ok(Ok(())) => Vec::new(),
+            Ok(Err(violations)) => violations,
+            Err(e) => vec![format!("validating the input {}", e)],
+        }
+    }
+
+    pub(crate) fn part_1(&self, variant: &str) -> Result<String, MyErr> {
+        match find_variant(D::variants_1(), variant) {
+            Some(solve) => solve(&self.input).into_answer(),
+            None if variant == DEFAULT_VARIANT => D::part_1(&self.input).into_answer(),
+            None => Err(MyErr::UnknownVariant(variant.to_string())),
+        }
+    }
+
+    pub(crate) fn part_2(&self, variant: &str) -> Result<String, MyErr> {
+        match find_variant(D::variants_2(), variant) {
+            Some(solve) => solve(&self.input).into_answer(),
+            None if variant == DEFAULT_VARIANT => D::part_2(&self.input).into_answer(),
+            None => Err(MyErr::UnknownVariant(variant.to_string())),
+        }
+    }
+
+    // The buffer is only dropped once part 2 is done with the input. Only the
+    // default variant gets to keep the input.
+    pub(crate) fn part_2_owned(self, variant: &str) -> Result<String, MyErr> {
+        match variant {
+            DEFAULT_VARIANT => D::part_2_owned(self.input).into_answer(),
+            variant => self.part_2(variant),
+        }
+    }
+}
+
+/// Runs a string parser over the whole of `input`, for days that implement
+/// `Day::parse_bytes` with nom's `&str` parsers
+pub fn parse_utf8<'a, O>(
+    input: &'a [u8],
+    parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> Result<O, MyErr> {
+    let (_, output) = all_consuming(parser)(std::str::from_utf8(input)?)?;
+    Ok(output)
 }
 
 pub trait Day {
-    type Input;
+    // Can borrow from the input, e.g. `Vec<&'a str>` of its lines
+    type Input<'a>;
+
+    // Everything that parses goes through here, from the input file's bytes. Days
+    // with nom's string parsers hand them to `parse_utf8`, heavy days can use nom's
+    // byte parsers and skip UTF-8 validation. Doesn't prepare the input, so the
+    // runner can time that on its own.
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, MyErr>;
 
-    fn parse(input_string: &str) -> IResult<&str, Self::Input>;
+    // Work both parts need, done once after parsing and timed on its own, e.g.
+    // filling in fields of the input that the parser left empty. `parse_str` runs
+    // it too, so tests get an input either part can use. Leave those fields `None`
+    // until then, so parts handed an unprepared input fail instead of answering wrong.
+    fn prepare(_input: &mut Self::Input<'_>) {}
 
-    type Output1: Display;
+    // Checks the properties of real inputs the solution relies on without the
+    // puzzle saying so, returning every one the input breaks. Runs after parsing,
+    // before `prepare`, and on its own with `run --check`.
+    fn validate(_input: &Self::Input<'_>) -> Result<(), Vec<String>> {
+        Ok(())
+    }
+
+    type Output1: Answer;
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1;
 
-    fn part_1(input: &Self::Input) -> Self::Output1;
+    type Output2: Answer;
 
-    type Output2: Display;
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2;
 
-    fn part_2(input: &Self::Input) -> Self::Output2;
+    // What the runner calls for part 2, with the input to keep. Simulations that
+    // mutate their input can override it to work in place, and implement `part_2`
+    // as `Self::part_2_owned(input.clone())` for callers that only lend the input.
+    fn part_2_owned(input: Self::Input<'_>) -> Self::Output2 {
+        Self::part_2(&input)
+    }
+
+    // Other ways of solving each part by name, e.g. a brute force kept around to
+    // check a cleverer solution against. `run --variant` picks one, and
+    // `crosscheck` runs them all.
+    fn variants_1() -> Variants<Self, Self::Output1>
+    where
+        Self: 'static,
+    {
+        &[]
+    }
 
-    fn parse_file(fp: &str) -> Result<Self::Input, MyErr> {
-        let input_string = read_to_string(fp)?;
-        let (_, input) = all_consuming(Self::parse)(&input_string)?;
+    fn variants_2() -> Variants<Self, Self::Output2>
+    where
+        Self: 'static,
+    {
+        &[]
+    }
+
+    // Parses and prepares the input, ready for either part, e.g. in tests
+    fn parse_str(input_string: &str) -> Result<Self::Input<'_>, MyErr> {
+        let mut input = Self::parse_bytes(input_string.as_bytes())?;
+        Self::prepare(&mut input);
         Ok(input)
     }
 
-    fn run_day(fp: &str) {
-        match Self::parse_file(fp) {
-            Err(e) => println!("Unable to parse file: {:?}", e),
-            Ok(input) => {
-                let before1 = Instant::now();
-                println!("Part 1: {}", Self::part_1(&input));
-                println!(
-                    "Part 1 took {}ms",
-                    before1.elapsed().as_nanos() as f32 / 1e6
-                );
-                let before2 = Instant::now();
-                println!("Part 2: {}", Self::part_2(&input));
-                println!(
-                    "Part 2 took {}ms",
-                    before2.elapsed().as_nanos() as f32 / 1e6
-                );
+    // Calls `on_part` as soon as each part finishes, so slow days show part 1 early
+    fn run_day(fp: &str, config: &RunConfig, on_part: &mut dyn FnMut(&PartReport)) -> DayReport
+    where
+        Self: Sized + 'static,
+        Self::Input<'static>: Send + Sync + 'static,
+    {
+        let measurement = Measurement::start();
+        let before = Instant::now();
+        let parsed = catch_panic(|| Parsed::<Self>::read(fp));
+        let parse_elapsed = before.elapsed();
+        let parse_alloc = measurement.finish();
+        let mut input = match parsed {
+            Ok(input) => input,
+            Err(e) => {
+                return DayReport {
+                    parse: Err(Failure::from(e)),
+                    parse_alloc,
+                    prepare: None,
+                    prepare_alloc: None,
+                    violations: Vec::new(),
+                    parts: Vec::new(),
+                }
             }
+        };
+        let violations = input.validate();
+        if config.check {
+            return DayReport {
+                parse: Ok(parse_elapsed),
+                parse_alloc,
+                prepare: None,
+                prepare_alloc: None,
+                violations,
+                parts: Vec::new(),
+            };
+        }
+        for violation in &violations {
+            warn!("The input breaks an assumption: {}", violation);
+        }
+        let measurement = Measurement::start();
+        let before = Instant::now();
+        let prepared = catch_panic(|| {
+            input.prepare();
+            Ok(())
+        });
+        let prepare = prepared.map(|_| before.elapsed()).map_err(Failure::from);
+        let prepare_alloc = measurement.finish();
+        let mut parts = Vec::new();
+        if prepare.is_ok() {
+            let input = Arc::new(input);
+            let variant_1 = pick_variant(Self::variants_1(), config);
+            let part1 = run_part(
+                1,
+                variant_1,
+                Arc::clone(&input),
+                config,
+                |input, variant| input.part_1(variant),
+            );
+            on_part(&part1);
+            // Part 2 gets the input to itself, unless part 1 timed out and is still
+            // using it; then it gets a freshly parsed copy
+            let input = Arc::try_unwrap(input).or_else(|_| {
+                catch_panic(|| {
+                    let mut input = Parsed::<Self>::read(fp)?;
+                    input.prepare();
+                    Ok(input)
+                })
+            });
+            let variant_2 = pick_variant(Self::variants_2(), config);
+            let part2 = match input {
+                Ok(input) => run_part(2, variant_2, input, config, |input, variant| {
+                    input.part_2_owned(variant)
+                }),
+                Err(e) => PartReport {
+                    part: 2,
+                    variant: None,
+                    answer: Err(Failure::from(e)),
+                    elapsed: Duration::ZERO,
+                    alloc: None,
+                },
+            };
+            on_part(&part2);
+            parts = vec![part1, part2];
+        }
+        DayReport {
+            parse: Ok(parse_elapsed),
+            parse_alloc,
+            prepare: Some(prepare),
+            prepare_alloc,
+            violations,
+            parts,
         }
     }
 }
@@ -0,0 +1,151 @@
+use std::any::Any;
+use std::time::{Duration, Instant};
+
+use crate::days::{catch_panic, Day, DayReport, Parsed, PartReport, RunConfig, DEFAULT_VARIANT};
+use crate::parser::MyErr;
+
+/// An input parsed by one day's `DynDay::parse_input`, to be solved by the same day
+pub struct ParsedInput(Box<dyn Any + Send + Sync>);
+
+/// A part's answer, and how long the part took to find it
+#[derive(Debug)]
+pub struct TimedAnswer {
+    pub answer: Result<String, MyErr>,
+    pub elapsed: Duration,
+}
+
+/// An object-safe view of a [`Day`], so days can be kept in collections and picked
+/// at runtime. Every `Day` implements it; call it through a reference to the day's
+/// unit struct, e.g. `&day04::Day04 as &dyn DynDay`.
+pub trait DynDay: Sync {
+    // Parses `input` and runs the day's `prepare` on it
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, MyErr>;
+
+    // Parses `input` without preparing it, so `prepare_input` can be timed on its own.
+    // The parts need a prepared input.
+    fn parse_unprepared(&self, input: &str) -> Result<ParsedInput, MyErr>;
+
+    // Runs the day's `prepare` on an input from `parse_unprepared`
+    fn prepare_input(&self, input: &mut ParsedInput) -> Result<(), MyErr>;
+
+    // Parses `input` where it is and throws the result away, so benches can time the
+    // parser without the copy `parse_input` makes for the result to borrow from
+    fn parse_only(&self, input: &[u8]) -> Result<(), MyErr>;
+
+    // Solves `part` on this thread, without a timeout. Panics if `input` was
+    // parsed by a different day.
+    fn solve_part(&self, input: &ParsedInput, part: usize) -> TimedAnswer;
+
+    // The assumptions the input breaks, see `Day::validate`
+    fn validate(&self, input: &ParsedInput) -> Vec<String>;
+
+    // The names of every way the day can solve `part`, starting with `DEFAULT_VARIANT`
+    fn variants(&self, part: usize) -> Vec<&'static str>;
+
+    // Whether either part has a variant called `name`
+    fn has_variant(&self, name: &str) -> bool;
+
+    // Like `solve_part`, with one of the part's `variants`
+    fn solve_variant(&self, input: &ParsedInput, part: usize, variant: &str) -> TimedAnswer;
+
+    // Like `solve_part`, but part 2 gets the input to keep (see `Day::part_2_owned`)
+    fn solve_part_owned(&self, input: ParsedInput, part: usize) -> TimedAnswer;
+
+    // The same as `Day::run_day`: parse the file, then run each part on a worker thread
+    fn run(&self, fp: &str, config: &RunConfig, on_part: &mut dyn FnMut(&PartReport)) -> DayReport;
+}
+
+impl<D> DynDay for D
+where
+    D: Day + Sync + 'static,
+    D::Input<'static>: Send + Sync + 'static,
+{
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, MyErr> {
+        let mut input = self.parse_unprepared(input)?;
+        self.prepare_input(&mut input)?;
+        Ok(input)
+    }
+
+    fn parse_unprepared(&self, input: &str) -> Result<ParsedInput, MyErr> {
+        // A copy of `input` for it to borrow from, as it must outlive the call
+        let input = catch_panic(|| Parsed::<D>::parse(Box::new(input.as_bytes().to_vec())))?;
+        Ok(ParsedInput(Box::new(input)))
+    }
+
+    fn prepare_input(&self, input: &mut ParsedInput) -> Result<(), MyErr> {
+        let input: &mut Parsed<D> = input
+            .0
+            .downcast_mut()
+            .expect("The input was parsed by a different day");
+        catch_panic(|| {
+            input.prepare();
+            Ok(())
+        })
+    }
+
+    fn parse_only(&self, input: &[u8]) -> Result<(), MyErr> {
+        catch_panic(|| D::parse_bytes(input).map(drop))
+    }
+
+    fn solve_part(&self, input: &ParsedInput, part: usize) -> TimedAnswer {
+        self.solve_variant(input, part, DEFAULT_VARIANT)
+    }
+
+    fn validate(&self, input: &ParsedInput) -> Vec<String> {
+        let input: &Parsed<D> = input
+            .0
+            .downcast_ref()
+            .expect("The input was parsed by a different day");
+        input.validate()
+    }
+
+    fn variants(&self, part: usize) -> Vec<&'static str> {
+        let names: Vec<&'static str> = match part {
+            1 => D::variants_1().iter().map(|(name, _)| *name).collect(),
+            2 => D::variants_2().iter().map(|(name, _)| *name).collect(),
+            _ => return Vec::new(),
+        };
+        [DEFAULT_VARIANT].into_iter().chain(names).collect()
+    }
+
+    fn has_variant(&self, name: &str) -> bool {
+        (1..=2).any(|part| self.variants(part).contains(&name))
+    }
+
+    fn solve_variant(&self, input: &ParsedInput, part: usize, variant: &str) -> TimedAnswer {
+        let input: &Parsed<D> = input
+            .0
+            .downcast_ref()
+            .expect("The input was parsed by a different day");
+        let before = Instant::now();
+        let answer = catch_panic(|| match part {
+            1 => input.part_1(variant),
+            2 => input.part_2(variant),
+            p => Err(MyErr::UnknownPart(p)),
+        });
+        TimedAnswer {
+            answer,
+            elapsed: before.elapsed(),
+        }
+    }
+
+    fn solve_part_owned(&self, input: ParsedInput, part: usize) -> TimedAnswer {
+        if part != 2 {
+            return self.solve_part(&input, part);
+        }
+        let input: Box<Parsed<D>> = input
+            .0
+            .downcast()
+            .expect("The input was parsed by a different day");
+        let before = Instant::now();
+        let answer = catch_panic(|| input.part_2_owned(DEFAULT_VARIANT));
+        TimedAnswer {
+            answer,
+            elapsed: before.elapsed(),
+        }
+    }
+
+    fn run(&self, fp: &str, config: &RunConfig, on_part: &mut dyn FnMut(&PartReport)) -> DayReport {
+        D::run_day(fp, config, on_part)
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs;
+
+use aoc_202x::{find_day, find_generator, DynDay};
+use fastrand::Rng;
+use indicatif::ProgressBar;
+
+const COUNTEREXAMPLE_DIR: &str = "inputs/fuzz";
+
+// What went wrong with a generated input, None if nothing did
+fn check(solver: &dyn DynDay, input: &str) -> Option<String> {
+    let parsed = match solver.parse_input(input) {
+        Ok(parsed) => parsed,
+        Err(e) => return Some(format!("Unable to parse the input: {}", e)),
+    };
+    let violations = solver.validate(&parsed);
+    if !violations.is_empty() {
+        return Some(format!(
+            "The input breaks an assumption: {}",
+            violations.join("; ")
+        ));
+    }
+    for part in 1..=2 {
+        let results: Vec<_> = solver
+            .variants(part)
+            .into_iter()
+            .map(|variant| (variant, solver.solve_variant(&parsed, part, variant).answer))
+            .collect();
+        let answers: Vec<_> = results
+            .iter()
+            .map(|(_, answer)| answer.as_ref().ok())
+            .collect();
+        if answers
+            .iter()
+            .any(|answer| answer.is_none() || *answer != answers[0])
+        {
+            let results: Vec<String> = results
+                .iter()
+                .map(|(variant, answer)| match answer {
+                    Ok(answer) => format!("{} says {}", variant, answer),
+                    Err(e) => format!("{} failed: {}", variant, e),
+                })
+                .collect();
+            return Some(format!("Part {}: {}", part, results.join(", ")));
+        }
+    }
+    None
+}
+
+// Solves `runs` generated inputs of `day` with every variant of each part, growing
+// from size 1 up to `size` so the first counterexample found tends to be small.
+// Stops at the first input that doesn't parse, breaks an assumption, makes a
+// variant fail or makes variants disagree, and saves it. Returns whether every
+// input was fine.
+pub fn fuzz(day: usize, runs: usize, size: usize, seed: Option<u64>) -> bool {
+    let solver = find_day(day).unwrap_or_else(|e| panic!("Could not fuzz day {}: {}", day, e));
+    let Some(generate) = find_generator(day) else {
+        panic!("Day {} has no input generator", day);
+    };
+    let seed = seed.unwrap_or_else(|| fastrand::u64(..));
+    println!("Fuzzing day {} with seed {}", day, seed);
+    let mut seeds = Rng::with_seed(seed);
+    let bar = ProgressBar::new(runs as u64);
+    for run in 0..runs {
+        // Each input gets its own seed, so it can be told apart from the others
+        let input_seed = seeds.u64(..);
+        let input = generate(&mut Rng::with_seed(input_seed), 1 + run * size / runs);
+        if let Some(problem) = check(solver, &input) {
+            bar.finish_and_clear();
+            fs::create_dir_all(COUNTEREXAMPLE_DIR).unwrap();
+            let path = format!(
+                "{}/day{:02}-{:016x}.txt",
+                COUNTEREXAMPLE_DIR, day, input_seed
+            );
+            fs::write(&path, &input).unwrap();
+            println!("{}", problem);
+            println!("Saved the input to {} after {} runs", path, run + 1);
+            return false;
+        }
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+    println!("All {} inputs were fine", runs);
+    true
+}
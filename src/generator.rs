@@ -0,0 +1,16 @@
+use fastrand::Rng;
+
+use crate::days::Day;
+
+/// Random puzzle inputs for a day, so `fuzz` can check its variants agree on more
+/// than the one real input. Inputs are the puzzle's text, so they go through the
+/// parser too.
+pub trait Generator: Day {
+    // A valid input, meeting every assumption `Day::validate` checks. `size`
+    // scales it, e.g. how many cards there are; the numbers themselves should
+    // stay small enough for brute force variants to finish quickly.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// A day's `Generator::generate`, as kept in `GENERATORS`
+pub type Generate = fn(&mut Rng, usize) -> String;
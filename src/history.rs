@@ -0,0 +1,209 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    process::Command,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aoc_202x::{DayReport, PartReport};
+
+const HISTORY_PATH: &str = "inputs/history.jsonl";
+
+/// One part of one run, as kept in the history file (one JSON object per line)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub time: DateTime<Utc>,
+    // From `git describe --always --dirty`, None outside a git checkout
+    pub commit: Option<String>,
+    pub day: usize,
+    pub example: bool,
+    pub part: usize,
+    // Set when the part was solved by one of the day's other variants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+// Every recorded run of `day` against the same input, oldest first
+pub fn load(day: usize, example: bool) -> Vec<Entry> {
+    let Ok(contents) = fs::read_to_string(HISTORY_PATH) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Entry>(line).ok())
+        .filter(|entry| entry.day == day && entry.example == example)
+        .collect()
+}
+
+pub fn record(day: usize, example: bool, report: &DayReport) {
+    let time = Utc::now();
+    let commit = commit();
+    let mut lines = String::new();
+    for part in &report.parts {
+        let entry = Entry {
+            time,
+            commit: commit.clone(),
+            day,
+            example,
+            part: part.part,
+            variant: part.variant.clone(),
+            answer: part.answer.clone().map_err(|e| e.message),
+            elapsed: part.elapsed,
+        };
+        lines += &(serde_json::to_string(&entry).unwrap() + "\n");
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)
+        .expect("Could not open the history file");
+    file.write_all(lines.as_bytes())
+        .expect("Could not write to the history file");
+}
+
+fn commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The fastest successful run of each part, failed and timed out runs don't count.
+// Neither do runs of other variants, only the default solutions are tracked.
+pub fn best(history: &[Entry]) -> [Option<&Entry>; 2] {
+    let mut best: [Option<&Entry>; 2] = [None, None];
+    for entry in history
+        .iter()
+        .filter(|entry| entry.answer.is_ok() && entry.variant.is_none())
+    {
+        let slot = &mut best[entry.part - 1];
+        if slot.is_none_or(|best| entry.elapsed < best.elapsed) {
+            *slot = Some(entry);
+        }
+    }
+    best
+}
+
+pub struct Regression<'a> {
+    pub part: usize,
+    pub percent: f64,
+    pub best: &'a Entry,
+}
+
+impl Display for Regression<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Part {} is {:.0}% slower than its best of {}ms",
+            self.part,
+            self.percent,
+            self.best.elapsed.as_nanos() as f32 / 1e6
+        )?;
+        match &self.best.commit {
+            Some(commit) => write!(f, " at {}", commit),
+            None => Ok(()),
+        }
+    }
+}
+
+// Some if `part` took more than `threshold` percent longer than the previous best
+pub fn compare<'a>(
+    part: &PartReport,
+    best: &[Option<&'a Entry>; 2],
+    threshold: f64,
+) -> Option<Regression<'a>> {
+    let best = best[part.part - 1]?;
+    if part.answer.is_err() || part.variant.is_some() || best.elapsed.is_zero() {
+        return None;
+    }
+    let percent = (part.elapsed.as_secs_f64() / best.elapsed.as_secs_f64() - 1.0) * 100.0;
+    (percent > threshold).then_some(Regression {
+        part: part.part,
+        percent,
+        best,
+    })
+}
+
+pub fn print_history(day: usize, example: bool) {
+    let history = load(day, example);
+    if history.is_empty() {
+        println!("No runs of day {} recorded yet", day);
+        return;
+    }
+    let best = best(&history);
+    for entry in &history {
+        let is_best = best[entry.part - 1].is_some_and(|best| std::ptr::eq(best, entry));
+        println!(
+            "{}  {:<16}  Part {}{}  {:>12}ms{}  {}",
+            entry.time.format("%Y-%m-%d %H:%M:%S"),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.part,
+            match &entry.variant {
+                Some(variant) => format!(" ({})", variant),
+                None => String::new(),
+            },
+            entry.elapsed.as_nanos() as f32 / 1e6,
+            if is_best { " (best)" } else { "       " },
+            match &entry.answer {
+                Ok(answer) => answer.clone(),
+                Err(e) => format!("failed: {}", e),
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use chrono::Utc;
+
+    use super::{best, compare, Entry};
+    use aoc_202x::PartReport;
+
+    fn entry(part: usize, millis: u64, answer: Result<&str, &str>) -> Entry {
+        Entry {
+            time: Utc::now(),
+            commit: None,
+            day: 1,
+            example: false,
+            part,
+            variant: None,
+            answer: answer.map(str::to_string).map_err(str::to_string),
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = [
+            entry(1, 10, Ok("142")),
+            entry(1, 8, Ok("142")),
+            entry(1, 1, Err("timed out")),
+            entry(2, 20, Ok("281")),
+        ];
+        let best = best(&history);
+        assert_eq!(best[0].unwrap().elapsed, Duration::from_millis(8));
+
+        let run = |part, millis| PartReport {
+            part,
+            variant: None,
+            answer: Ok(String::new()),
+            elapsed: Duration::from_millis(millis),
+            alloc: None,
+        };
+        assert!(compare(&run(1, 8), &best, 10.0).is_none());
+        assert_eq!(compare(&run(1, 12), &best, 10.0).unwrap().percent, 50.0);
+        assert!(compare(&run(2, 21), &best, 10.0).is_none());
+    }
+}
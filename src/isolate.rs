@@ -0,0 +1,218 @@
+use std::{
+    env,
+    fs::File,
+    io::Read,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::OnceLock,
+};
+
+use aoc_202x::{find_day, DayReport};
+use serde::Serialize;
+
+use crate::RunOptions;
+
+/// How a day's child process ended, next to whatever report it printed
+#[derive(Debug, Default, Serialize)]
+pub struct ChildStatus {
+    // Set when the child died instead of printing its report
+    pub crash: Option<String>,
+    // Maximum resident set size in bytes (Linux only)
+    pub peak_rss: Option<u64>,
+}
+
+// The parent's logging flags, and its log file, for children to log the same way
+static LOGGING: OnceLock<(Vec<String>, Option<File>)> = OnceLock::new();
+
+// Children log into `log_file` through their stderr, as opening it again would
+// truncate what the parent already wrote
+pub fn forward_logging(flags: Vec<String>, log_file: Option<File>) {
+    let _ = LOGGING.set((flags, log_file));
+}
+
+pub struct ChildRun {
+    pub report: Option<DayReport>,
+    pub status: ChildStatus,
+}
+
+// Runs `day` in a fresh copy of this binary, so stack overflows, aborts and
+// resource limits only take that day down
+pub fn run_day(day: usize, options: &RunOptions) -> ChildRun {
+    let mut command = Command::new(env::current_exe().expect("Could not find own executable"));
+    // The parent records the child's report in the history itself
+    command.args(["run", &day.to_string(), "--json", "--no-history"]);
+    if options.example {
+        command.arg("--example");
+    }
+    if let Some(timeout) = options.timeout {
+        command.args([
+            "--timeout",
+            &humantime::format_duration(timeout).to_string(),
+        ]);
+    }
+    // The child runs a single day, which must have the variant, so days without it
+    // are left to their default here
+    if let Some(variant) = options
+        .variant
+        .as_ref()
+        .filter(|variant| find_day(day).is_ok_and(|solver| solver.has_variant(variant)))
+    {
+        command.args(["--variant", variant]);
+    }
+    if options.check {
+        command.arg("--check");
+    }
+    if let Some((flags, log_file)) = LOGGING.get() {
+        command.args(flags);
+        if let Some(file) = log_file.as_ref().and_then(|file| file.try_clone().ok()) {
+            command.stderr(file);
+        }
+    }
+    #[cfg(target_os = "linux")]
+    limits::apply(&mut command, options);
+
+    let mut child = match command.stdout(Stdio::piped()).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return ChildRun {
+                report: None,
+                status: ChildStatus {
+                    crash: Some(format!("could not start: {}", e)),
+                    peak_rss: None,
+                },
+            }
+        }
+    };
+    let mut stdout = String::new();
+    let read = child.stdout.take().unwrap().read_to_string(&mut stdout);
+    let (exit, peak_rss) = wait(&mut child);
+
+    let report = stdout
+        .lines()
+        .last()
+        .and_then(|line| serde_json::from_str::<DayReport>(line).ok());
+    let crash = match (&report, read) {
+        (_, Err(e)) => Some(format!("could not read its output: {}", e)),
+        (Some(_), Ok(_)) if exit.success() => None,
+        // Failing `run --check` exits with 1 after the report, which says why
+        (Some(_), Ok(_)) if options.check && exit.code() == Some(1) => None,
+        (_, Ok(_)) => Some(describe_exit(exit)),
+    };
+    ChildRun {
+        report: report.filter(|_| crash.is_none()),
+        status: ChildStatus { crash, peak_rss },
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn wait(child: &mut Child) -> (ExitStatus, Option<u64>) {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status = 0;
+    // SAFETY: rusage is plain old data, and we reap our own child exactly once
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    let pid = child.id() as libc::pid_t;
+    match unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } {
+        -1 => (child.wait().unwrap(), None),
+        // ru_maxrss is in kilobytes on Linux
+        _ => (
+            ExitStatus::from_raw(status),
+            Some(usage.ru_maxrss as u64 * 1024),
+        ),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn wait(child: &mut Child) -> (ExitStatus, Option<u64>) {
+    (child.wait().unwrap(), None)
+}
+
+#[cfg(unix)]
+fn describe_exit(exit: ExitStatus) -> String {
+    use std::os::unix::process::ExitStatusExt;
+
+    match exit.signal() {
+        // SAFETY: strsignal returns a pointer to a static, NUL-terminated string
+        Some(signal) => format!("killed by signal {} ({})", signal, unsafe {
+            std::ffi::CStr::from_ptr(libc::strsignal(signal)).to_string_lossy()
+        }),
+        None => format!("exited with {}", exit),
+    }
+}
+
+#[cfg(not(unix))]
+fn describe_exit(exit: ExitStatus) -> String {
+    format!("exited with {}", exit)
+}
+
+#[cfg(target_os = "linux")]
+mod limits {
+    use std::{io, os::unix::process::CommandExt, process::Command};
+
+    use crate::RunOptions;
+
+    pub fn apply(command: &mut Command, options: &RunOptions) {
+        let memory = options.memory_limit;
+        let cpu = options.cpu_limit.map(|cpu| cpu.as_secs().max(1));
+        // SAFETY: only async-signal-safe setrlimit calls between fork and exec
+        unsafe {
+            command.pre_exec(move || {
+                let set = |resource, limit| {
+                    let limit = libc::rlimit {
+                        rlim_cur: limit,
+                        rlim_max: limit,
+                    };
+                    match libc::setrlimit(resource, &limit) {
+                        0 => Ok(()),
+                        _ => Err(io::Error::last_os_error()),
+                    }
+                };
+                // Crashes are expected here, don't litter the directory with cores
+                set(libc::RLIMIT_CORE, 0)?;
+                if let Some(memory) = memory {
+                    set(libc::RLIMIT_AS, memory)?;
+                }
+                if let Some(cpu) = cpu {
+                    set(libc::RLIMIT_CPU, cpu)?;
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+// Parses sizes like 512M or 2GiB into bytes
+pub fn parse_size(size: &str) -> Result<u64, String> {
+    let size = size.trim();
+    let digits = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(digits);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("{} does not start with a number", size))?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(format!("unknown unit {} in {}", unit, size)),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("{} is too large", size))
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_size;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("512M"), Ok(512 << 20));
+        assert_eq!(parse_size("2GiB"), Ok(2 << 30));
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("3T").is_err());
+        assert!(parse_size("99999999999G").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::calendar;
+use crate::client;
+
+// The site asks for private leaderboards to be fetched at most once every 15 minutes
+const CACHE_FOR: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    // Null for anonymous users
+    name: Option<String>,
+    local_score: u64,
+    stars: u64,
+    // Day -> part -> star
+    completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star_time(&self, day: usize, part: usize) -> Option<DateTime<Utc>> {
+        let star = self
+            .completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())?;
+        DateTime::from_timestamp(star.get_star_ts, 0)
+    }
+}
+
+fn cache_path(year: usize, id: &str) -> String {
+    format!("inputs/leaderboard-{}-{}.json", year, id)
+}
+
+// The leaderboard's JSON, from the cache when it was fetched less than 15 minutes ago
+fn fetch(year: usize, id: &str) -> String {
+    let path = cache_path(year, id);
+    let fresh = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .is_ok_and(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .is_ok_and(|age| age < CACHE_FOR)
+        });
+    if fresh {
+        return fs::read_to_string(&path).unwrap();
+    }
+    let response = client::get(&format!("{}/leaderboard/private/view/{}.json", year, id));
+    // Without a valid session the site redirects to a login page instead of failing
+    let json = match response.status().is_success() {
+        true => response.text().unwrap(),
+        false => String::new(),
+    };
+    if serde_json::from_str::<Leaderboard>(&json).is_err() {
+        panic!(
+            "Could not get leaderboard {}. Is your correct session cookie in your .session file, and are you a member?",
+            id
+        )
+    }
+    fs::write(&path, &json).unwrap();
+    json
+}
+
+pub fn show(year: usize, id: &str, day: Option<usize>) {
+    let leaderboard: Leaderboard = serde_json::from_str(&fetch(year, id)).unwrap();
+    print!("{}", render(&leaderboard, year, day));
+}
+
+// Highest score first, ties broken by stars and then by name
+fn ranking(leaderboard: &Leaderboard) -> Vec<&Member> {
+    let mut members: Vec<&Member> = leaderboard.members.values().collect();
+    members.sort_by_key(|member| {
+        (
+            std::cmp::Reverse(member.local_score),
+            std::cmp::Reverse(member.stars),
+            member.name(),
+        )
+    });
+    members
+}
+
+// Time since the puzzle unlocked, as hours:minutes:seconds
+fn since_unlock(year: usize, day: usize, time: Option<DateTime<Utc>>) -> String {
+    match time {
+        Some(time) => format_delta(time - calendar::unlock_time(year, day)),
+        None => String::from("-"),
+    }
+}
+
+fn format_delta(delta: chrono::Duration) -> String {
+    let secs = delta.num_seconds();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+// The ranking with a star strip per member (`*` both parts, `+` part 1 only),
+// followed by star times for `day`, or the latest day anyone has a star for
+fn render(leaderboard: &Leaderboard, year: usize, day: Option<usize>) -> String {
+    let members = ranking(leaderboard);
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:>4} {:>5} {:>5}  {:<25}  Name",
+        "", "Score", "Stars", "Days 1-25"
+    )
+    .unwrap();
+    for (rank, member) in members.iter().enumerate() {
+        let strip: String = (1..=25)
+            .map(
+                |day| match (member.star_time(day, 1), member.star_time(day, 2)) {
+                    (Some(_), Some(_)) => '*',
+                    (Some(_), None) => '+',
+                    _ => '.',
+                },
+            )
+            .collect();
+        writeln!(
+            out,
+            "{:>3}) {:>5} {:>5}  {}  {}",
+            rank + 1,
+            member.local_score,
+            member.stars,
+            strip,
+            member.name()
+        )
+        .unwrap();
+    }
+
+    let latest = (1..=25).rev().find(|day| {
+        members
+            .iter()
+            .any(|member| member.star_time(*day, 1).is_some())
+    });
+    let Some(day) = day.or(latest) else {
+        return out;
+    };
+    // Fastest to both stars first, then fastest to the first
+    let mut solvers: Vec<&&Member> = members
+        .iter()
+        .filter(|member| member.star_time(day, 1).is_some())
+        .collect();
+    solvers.sort_by_key(|member| {
+        (
+            member.star_time(day, 2).is_none(),
+            member.star_time(day, 2),
+            member.star_time(day, 1),
+        )
+    });
+    writeln!(out, "\nDay {}{:<22}Part 1    Part 2    Delta", day, "").unwrap();
+    for member in solvers {
+        let (part_1, part_2) = (member.star_time(day, 1), member.star_time(day, 2));
+        let delta = match (part_1, part_2) {
+            (Some(part_1), Some(part_2)) => format_delta(part_2 - part_1),
+            _ => String::from("-"),
+        };
+        writeln!(
+            out,
+            "{:<25} {:>8}  {:>8}  {:>8}",
+            member.name(),
+            since_unlock(year, day, part_1),
+            since_unlock(year, day, part_2),
+            delta
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ranking, render, Leaderboard};
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard.json");
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard: Leaderboard = serde_json::from_str(FIXTURE).unwrap();
+        let names: Vec<String> = ranking(&leaderboard).iter().map(|m| m.name()).collect();
+        assert_eq!(names, ["Alice", "(anonymous user #2)", "Carol"]);
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard: Leaderboard = serde_json::from_str(FIXTURE).unwrap();
+        let table = render(&leaderboard, 2023, None);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[1],
+            "  1)     8     3  *+.......................  Alice"
+        );
+        assert_eq!(
+            lines[3],
+            "  3)     0     0  .........................  Carol"
+        );
+        // Day 2 is the latest with a star
+        assert!(lines[5].starts_with("Day 2"));
+        assert_eq!(
+            lines[6],
+            "Alice                     00:10:00         -         -"
+        );
+
+        let table = render(&leaderboard, 2023, Some(1));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[6],
+            "Alice                     00:05:00  00:10:00  00:05:00"
+        );
+        assert_eq!(
+            lines[7],
+            "(anonymous user #2)       00:03:20  00:36:40  00:33:20"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Advent of Code solutions behind the [`Day`] trait, usable from the CLI, benches
+//! and tests alike.
+
+pub mod alloc;
+pub mod cancel;
+pub mod days;
+pub mod dyn_day;
+pub mod generator;
+pub mod parser;
+pub mod progress;
+
+use days::*;
+
+pub use days::{
+    Answer, Day, DayReport, Failure, FailureKind, PartReport, RunConfig, Shown, Variants,
+    DEFAULT_VARIANT,
+};
+pub use dyn_day::{DynDay, ParsedInput, TimedAnswer};
+pub use generator::{Generate, Generator};
+pub use parser::MyErr as Error;
+
+/// The year the days in `days` are solutions for
+pub const YEAR: usize = 2023;
+
+/// Every day there is a solution for, in order
+pub static DAYS: &[(usize, &dyn DynDay)] = &[
+    (1, &day01::Day01),
+    (2, &day02::Day02),
+    (3, &day03::Day03),
+    (4, &day04::Day04),
+    (5, &day05::Day05),
+    (6, &day06::Day06),
+    (7, &day07::Day07),
+    (8, &day08::Day08),
+    (9, &day09::Day09),
+    (10, &day10::Day10),
+    (11, &day11::Day11),
+    (12, &day12::Day12),
+    (13, &day13::Day13),
+    (14, &day14::Day14),
+    (15, &day15::Day15),
+    (16, &day16::Day16),
+    (17, &day17::Day17),
+    (18, &day18::Day18),
+    (19, &day19::Day19),
+    (20, &day20::Day20),
+    (21, &day21::Day21),
+    (22, &day22::Day22),
+    (23, &day23::Day23),
+    (24, &day24::Day24),
+    (25, &day25::Day25),
+];
+
+/// Every day that can generate random inputs for itself, in order
+pub static GENERATORS: &[(usize, Generate)] = &[
+    (4, day04::Day04::generate),
+    (5, day05::Day05::generate),
+    (6, day06::Day06::generate),
+];
+
+pub fn find_day(day: usize) -> Result<&'static dyn DynDay, Error> {
+    DAYS.iter()
+        .find(|(number, _)| *number == day)
+        .map(|(_, day)| *day)
+        .ok_or(Error::UnknownDay(day))
+}
+
+pub fn find_generator(day: usize) -> Option<Generate> {
+    GENERATORS
+        .iter()
+        .find(|(number, _)| *number == day)
+        .map(|(_, generate)| *generate)
+}
+
+/// Parses `input` and solves one part of a day with it on the calling thread. A
+/// part that panics (like an `unimplemented!` stub) is returned as an error.
+pub fn solve(year: usize, day: usize, part: usize, input: &str) -> Result<String, Error> {
+    if year != YEAR {
+        return Err(Error::UnknownYear(year));
+    }
+    let day = find_day(day)?;
+    day.solve_part_owned(day.parse_input(input)?, part).answer
+}
+
+/// Runs both parts of `day` on the input in `fp`, each on its own worker thread,
+/// calling `on_part` as soon as each part finishes. A `config.variant` must be
+/// one of some day's variants; parts without it solve with their default, so the
+/// same config can run every day.
+pub fn run_day(
+    day: usize,
+    fp: &str,
+    config: &RunConfig,
+    on_part: &mut dyn FnMut(&PartReport),
+) -> Result<DayReport, Error> {
+    let day = find_day(day)?;
+    if let Some(variant) = &config.variant {
+        if !DAYS.iter().any(|(_, day)| day.has_variant(variant)) {
+            return Err(Error::UnknownVariant(variant.clone()));
+        }
+    }
+    Ok(day.run(fp, config, on_part))
+}
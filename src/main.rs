@@ -1,14 +1,33 @@
-use std::{fs, time::Instant};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+};
 
-use chrono::{Datelike, Local};
-use clap::{Parser, Subcommand};
+use chrono::Utc;
+use clap::{ArgAction, Args, Parser, Subcommand};
+use env_logger::{Target, WriteStyle};
+use log::LevelFilter;
 
-use days::*;
+use answers::{answers_path, Answers};
+use aoc_202x::{progress, DayReport, PartReport, RunConfig, DAYS, YEAR};
 
-mod days;
-mod parser;
-
-const YEAR: usize = 2023;
+mod answers;
+mod calendar;
+mod client;
+mod crosscheck;
+mod fuzz;
+mod history;
+mod isolate;
+mod leaderboard;
+mod readme;
+mod report;
+mod scaffold;
+mod stats;
+mod timer;
+mod watch;
 
 #[derive(Parser)]
 #[command(author, version)]
@@ -16,6 +35,97 @@ const YEAR: usize = 2023;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = ArgAction::Count,
+        help = "Shows more log output (-v for info, -vv for debug, -vvv for trace)"
+    )]
+    verbose: u8,
+    #[arg(
+        short,
+        long,
+        global = true,
+        conflicts_with = "verbose",
+        help = "Only logs errors"
+    )]
+    quiet: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Writes log output to a file instead of stderr"
+    )]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct RunOptions {
+    #[arg(
+        short,
+        long,
+        help = "Runs on the example input instead of the real one"
+    )]
+    example: bool,
+    #[arg(long, help = "Prints one line of JSON per day instead of text")]
+    json: bool,
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        help = "Gives up on a part after this long, e.g. 30s or 2m"
+    )]
+    timeout: Option<std::time::Duration>,
+    #[arg(
+        long,
+        help = "Runs each day in its own child process, so crashes only take that day down"
+    )]
+    isolate: bool,
+    #[arg(
+        long,
+        value_name = "SIZE",
+        requires = "isolate",
+        value_parser = isolate::parse_size,
+        help = "Limits each isolated day's address space (Linux only), e.g. 2G. Parts get a quarter of it as stack"
+    )]
+    memory_limit: Option<u64>,
+    #[arg(
+        long,
+        value_name = "DURATION",
+        requires = "isolate",
+        value_parser = humantime::parse_duration,
+        help = "Limits each isolated day's CPU time (Linux only), e.g. 1m"
+    )]
+    cpu_limit: Option<std::time::Duration>,
+    #[arg(long, help = "Does not record this run's timings in the history")]
+    no_history: bool,
+    #[arg(
+        long,
+        conflicts_with = "json",
+        help = "Flags parts that got slower than their best recorded run"
+    )]
+    compare: bool,
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        default_value_t = 10.0,
+        requires = "compare",
+        help = "How much slower than its best a part must be to be flagged"
+    )]
+    threshold: f64,
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Solves each part with this variant, where the day has one by that name"
+    )]
+    variant: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["compare", "variant"],
+        help = "Only parses the input and checks the assumptions the solution makes about it"
+    )]
+    check: bool,
 }
 
 #[derive(Subcommand)]
@@ -25,6 +135,8 @@ enum Commands {
         day: Option<String>,
         #[arg(short, long, help = "Runs all days sequentially")]
         all: bool,
+        #[command(flatten)]
+        options: RunOptions,
     },
     GetInput {
         #[arg(
@@ -34,62 +146,310 @@ enum Commands {
         day: Option<String>,
         #[arg(short, long, help = "Downloads input for all days sequentially")]
         all: bool,
+        #[arg(
+            short,
+            long,
+            conflicts_with = "all",
+            help = "Waits for the puzzle to unlock, then downloads its input straight away"
+        )]
+        wait: bool,
+        #[arg(short, long, help = "Also downloads the puzzle text")]
+        puzzle: bool,
+    },
+    Answer {
+        #[arg(value_name = "DAY", help = "The number of the day the answer is for")]
+        day: String,
+        #[arg(value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        #[arg(value_name = "ANSWER", help = "The answer the site accepted")]
+        answer: String,
+        #[arg(short, long, help = "Records the answer for the example input")]
+        example: bool,
+    },
+    Crosscheck {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day whose variants you want to compare"
+        )]
+        day: String,
+        #[arg(short, long, help = "Checks against the example input instead")]
+        example: bool,
+    },
+    Fuzz {
+        #[arg(value_name = "DAY", help = "The number of the day you want to fuzz")]
+        day: String,
+        #[arg(
+            short,
+            long,
+            default_value_t = 1000,
+            help = "How many inputs to generate"
+        )]
+        runs: usize,
+        #[arg(
+            short,
+            long,
+            default_value_t = 20,
+            help = "How large the last inputs get, starting from 1"
+        )]
+        size: usize,
+        #[arg(
+            long,
+            help = "Generates the same inputs as an earlier run with this seed"
+        )]
+        seed: Option<u64>,
+    },
+    History {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you want to see past runs of"
+        )]
+        day: String,
+        #[arg(short, long, help = "Shows runs on the example input instead")]
+        example: bool,
+    },
+    Leaderboard {
+        #[arg(value_name = "ID", help = "The id of the private leaderboard")]
+        id: String,
+        #[arg(
+            short,
+            long,
+            help = "The day to show star times for, the latest one anyone has a star for by default"
+        )]
+        day: Option<String>,
+    },
+    Readme,
+    Start {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you are starting, today by default"
+        )]
+        day: Option<String>,
+    },
+    Stats {
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            help = "Reads a saved copy of your personal stats page instead of fetching it"
+        )]
+        file: Option<String>,
+    },
+    Timer {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you want to see solve times for, every day by default"
+        )]
+        day: Option<String>,
+    },
+    Watch {
+        #[arg(value_name = "DAY", help = "The number of the day you want to watch")]
+        day: String,
+    },
+    NewDay {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you want to scaffold"
+        )]
+        day: String,
+    },
+    NewYear {
+        #[arg(value_name = "YEAR", help = "The year you want to start solving")]
+        year: usize,
     },
 }
 
 fn main() {
     let cli = Cli::parse();
+    init_logging(&cli);
 
     match &cli.command {
-        Commands::Run { day, all } => {
-            if *all {
-                run_all_days();
+        Commands::Run { day, all, options } => {
+            if options.json {
+                progress::silence();
+            }
+            let reports = if *all {
+                check_variant(None, options);
+                run_all_days(options)
             } else {
-                match day {
-                    Some(day) => run_day(parse_day(day)),
+                let day = match day {
+                    Some(day) => parse_day(day),
                     None => {
-                        println!("No day parameter specified, attempting to run today");
                         let now_day = get_today();
-                        println!("Running day {}", now_day);
-                        run_day(now_day);
+                        if !options.json {
+                            println!("No day parameter specified, attempting to run today");
+                            println!("Running day {}", now_day);
+                        }
+                        now_day
                     }
-                }
+                };
+                check_variant(Some(day), options);
+                vec![run_day(day, options)]
+            };
+            let failed = reports
+                .iter()
+                .any(|report| report::check_failed(report.as_ref(), *all));
+            if options.check && failed {
+                std::process::exit(1);
             }
         }
-        Commands::GetInput { day, all } => {
+        Commands::GetInput {
+            day,
+            all,
+            wait,
+            puzzle,
+        } => {
             if *all {
-                download_all_input();
+                download_all_input(*puzzle);
             } else {
-                match day {
-                    Some(day) => download_input(parse_day(day)),
-                    None => {
-                        println!(
-                            "No day parameter specified, attempting to download today's input"
-                        );
-                        let now_day = get_today();
-                        println!("Getting input for day {}", now_day);
-                        download_input(now_day);
+                let day = if *wait {
+                    wait_for_unlock(day.as_deref().map(parse_day))
+                } else {
+                    match day {
+                        Some(day) => parse_day(day),
+                        None => {
+                            println!(
+                                "No day parameter specified, attempting to download today's input"
+                            );
+                            let now_day = get_today();
+                            println!("Getting input for day {}", now_day);
+                            now_day
+                        }
                     }
-                }
+                };
+                download_input(day, *puzzle, *wait);
+                // Getting the input is as good a sign as any that we've started
+                timer::start(day);
+            }
+        }
+        Commands::Answer {
+            day,
+            part,
+            answer,
+            example,
+        } => {
+            let day = parse_day(day);
+            let path = answers_path(day, *example);
+            let mut answers = Answers::load(&path);
+            answers.set(*part as usize, answer);
+            answers.save(&path);
+            if !*example {
+                timer::solved(day, *part as usize);
             }
+            println!(
+                "Recorded {} as the answer to part {} in {}",
+                answer, part, path
+            );
         }
+        Commands::Crosscheck { day, example } => {
+            if !crosscheck::crosscheck(parse_day(day), *example) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Fuzz {
+            day,
+            runs,
+            size,
+            seed,
+        } => {
+            if !fuzz::fuzz(parse_day(day), *runs, *size, *seed) {
+                std::process::exit(1);
+            }
+        }
+        Commands::History { day, example } => history::print_history(parse_day(day), *example),
+        Commands::Leaderboard { id, day } => {
+            leaderboard::show(YEAR, id, day.as_deref().map(parse_day))
+        }
+        Commands::Readme => readme::update(YEAR),
+        Commands::Start { day } => timer::start(day.as_deref().map_or_else(get_today, parse_day)),
+        Commands::Stats { file } => stats::show(YEAR, file.as_deref()),
+        Commands::Timer { day } => timer::show(YEAR, day.as_deref().map(parse_day)),
+        Commands::Watch { day } => watch::watch(parse_day(day)),
+        Commands::NewDay { day } => scaffold::new_day(parse_day(day)),
+        Commands::NewYear { year } => scaffold::new_year(*year),
     }
 }
 
+// Solutions log through the `log` macros; nothing below a warning is shown by default
+fn init_logging(cli: &Cli) {
+    let level = match (cli.quiet, cli.verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Info,
+        (false, 2) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    };
+    let mut builder = env_logger::Builder::new();
+    // Keep dependencies (reqwest and friends) at warnings unless RUST_LOG says otherwise
+    builder
+        .filter_level(level.min(LevelFilter::Warn))
+        .filter_module(env!("CARGO_CRATE_NAME"), level);
+    // RUST_LOG can still narrow things down, e.g. RUST_LOG=aoc_202x::days::day05=trace
+    if let Ok(filters) = env::var("RUST_LOG") {
+        builder.parse_filters(&filters);
+    }
+    // For isolated children, which get the log file as stderr instead of --log-file
+    let flags = match (cli.quiet, cli.verbose) {
+        (true, _) => vec!["--quiet".to_string()],
+        (false, 0) => Vec::new(),
+        (false, verbose) => vec![format!("-{}", "v".repeat(verbose as usize))],
+    };
+    let mut log_file = None;
+    if let Some(path) = &cli.log_file {
+        let file = File::create(path).expect("Could not create log file");
+        log_file = file.try_clone().ok();
+        builder
+            .target(Target::Pipe(Box::new(file)))
+            .write_style(WriteStyle::Never);
+    }
+    builder.init();
+    isolate::forward_logging(flags, log_file);
+}
+
 fn get_today() -> usize {
-    let now = Local::now();
-    let now_day = now.day();
-    if now.month() == 12 && (1..=25).contains(&now_day) {
-        now_day.try_into().unwrap()
-    } else {
-        panic!("Today is not a valid Advent of Code day. Please specify a day");
+    match calendar::puzzle_day(Utc::now()) {
+        Some(now_day) => now_day,
+        None => panic!("Today is not a valid Advent of Code day. Please specify a day"),
     }
 }
 
+// Shows a countdown until `day` (or the next puzzle) unlocks, returning that day
+fn wait_for_unlock(day: Option<usize>) -> usize {
+    let (day, unlock) = match day {
+        Some(day) => (day, calendar::unlock_time(YEAR, day)),
+        None => match calendar::next_unlock(YEAR, Utc::now()) {
+            Some(next) => next,
+            None => panic!("Every puzzle of {} has already unlocked", YEAR),
+        },
+    };
+    loop {
+        let remaining = (unlock - Utc::now()).num_milliseconds();
+        if remaining <= 0 {
+            break;
+        }
+        let secs = (remaining + 999) / 1000;
+        print!(
+            "\rDay {} unlocks in {:02}:{:02}:{:02} ",
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        );
+        io::stdout().flush().unwrap();
+        // Sleep until the countdown next ticks over
+        let tick = match remaining % 1000 {
+            0 => 1000,
+            ms => ms,
+        };
+        thread::sleep(std::time::Duration::from_millis(tick as u64));
+    }
+    println!("\rDay {} is unlocked!            ", day);
+    day
+}
+
 fn parse_day(day: &str) -> usize {
     match day.parse() {
         Ok(i) => {
-            if (i..=25).contains(&i) {
+            if (1..=25).contains(&i) {
                 i
             } else {
                 panic!("{} is not a valid day. Only days 1-25 are allowed.", i)
@@ -101,71 +461,200 @@ fn parse_day(day: &str) -> usize {
     }
 }
 
-fn run_all_days() {
-    (1..=25).map(run_day).collect()
+fn input_path(day: usize) -> String {
+    format!("inputs/day{:02}.txt", day)
+}
+
+fn example_path(day: usize) -> String {
+    format!("inputs/day{:02}.example.txt", day)
 }
+
+fn puzzle_path(day: usize) -> String {
+    format!("inputs/day{:02}.puzzle.txt", day)
+}
+
+// Writes a new file and renames it over `path` instead of rewriting `path` in place,
+// so a run that has the old input mapped (with `--features mmap`) keeps its contents
+fn replace_file(path: &str, contents: &str) {
+    let temp = format!("{}.tmp", path);
+    fs::write(&temp, contents).unwrap();
+    fs::rename(&temp, path).unwrap();
+}
+
+fn run_all_days(options: &RunOptions) -> Vec<Option<DayReport>> {
+    let days: Vec<_> = (1..=25)
+        .map(|day| {
+            let report = run_day(day, options);
+            (
+                day,
+                report,
+                Answers::load(&answers_path(day, options.example)),
+            )
+        })
+        .collect();
+    if !options.json && !options.check {
+        report::print_summary(&days);
+    }
+    days.into_iter().map(|(_, report, _)| report).collect()
+}
+// Exits if `day` doesn't have the variant asked for. With `--all` (no `day`), days
+// without it run their default instead, as long as some day has it.
+fn check_variant(day: Option<usize>, options: &RunOptions) {
+    let Some(variant) = &options.variant else {
+        return;
+    };
+    let known = DAYS
+        .iter()
+        .filter(|(number, _)| day.is_none_or(|day| day == *number))
+        .any(|(_, solver)| solver.has_variant(variant));
+    if !known {
+        match day {
+            Some(day) => println!("Day {} has no variant called {}", day, variant),
+            None => println!("No day has a variant called {}", variant),
+        }
+        std::process::exit(1);
+    }
+}
+
 // Panics if you provide a value outside the range of 1 to 25
-fn run_day(day: usize) {
-    println!("======== DAY {} ========", day);
+fn run_day(day: usize, options: &RunOptions) -> Option<DayReport> {
+    if !options.json {
+        println!("======== DAY {} ========", day);
+    }
     // I'd like to do this with a macro, but I'm not sure how to do it.
-    let input_fp = &format!("inputs/day{:02}.txt", day);
-    match day {
-        1 => day01::Day01::run_day(input_fp),
-        2 => day02::Day02::run_day(input_fp),
-        3 => day03::Day03::run_day(input_fp),
-        4 => day04::Day04::run_day(input_fp),
-        5 => day05::Day05::run_day(input_fp),
-        6 => day06::Day06::run_day(input_fp),
-        7 => day07::Day07::run_day(input_fp),
-        8 => day08::Day08::run_day(input_fp),
-        9 => day09::Day09::run_day(input_fp),
-        10 => day10::Day10::run_day(input_fp),
-        11 => day11::Day11::run_day(input_fp),
-        12 => day12::Day12::run_day(input_fp),
-        13 => day13::Day13::run_day(input_fp),
-        14 => day14::Day14::run_day(input_fp),
-        15 => day15::Day15::run_day(input_fp),
-        16 => day16::Day16::run_day(input_fp),
-        17 => day17::Day17::run_day(input_fp),
-        18 => day18::Day18::run_day(input_fp),
-        19 => day19::Day19::run_day(input_fp),
-        20 => day20::Day20::run_day(input_fp),
-        21 => day21::Day21::run_day(input_fp),
-        22 => day22::Day22::run_day(input_fp),
-        23 => day23::Day23::run_day(input_fp),
-        24 => day24::Day24::run_day(input_fp),
-        25 => day25::Day25::run_day(input_fp),
-        d => panic!("Provided unsupported day {}", d),
-    }
-}
-
-fn download_all_input() {
-    (1..=25).map(download_input).collect()
-}
-
-fn download_input(day: usize) {
-    // Read session cookie from .session file
-    let session = fs::read_to_string(".session").expect("Could not find .session file");
-    let session = session.trim();
-    let url = format!("https://adventofcode.com/{}/day/{}/input", YEAR, day);
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .get(url)
-        .header("cookie", format!("session={};", session))
-        .send()
-        .unwrap();
+    let input_fp = &match options.example {
+        true => example_path(day),
+        false => input_path(day),
+    };
+    let answers = &Answers::load(&answers_path(day, options.example));
+    let history = history::load(day, options.example);
+    let best = &history::best(&history);
+    let print_part = |part: &PartReport| {
+        report::print_part(part, answers);
+        if options.compare {
+            if let Some(regression) = history::compare(part, best, options.threshold) {
+                println!("{}", regression);
+            }
+        }
+    };
+    if options.isolate {
+        let child = isolate::run_day(day, options);
+        if let Some(day_report) = child.report.as_ref().filter(|_| !options.no_history) {
+            history::record(day, options.example, day_report);
+        }
+        if options.json {
+            report::print_json(
+                day,
+                options.example,
+                child.report.as_ref(),
+                answers,
+                Some(&child.status),
+            );
+            return child.report;
+        }
+        if let Some(day_report) = &child.report {
+            day_report.parts.iter().for_each(print_part);
+            print_day_end(day_report, options.check);
+        }
+        report::print_child(&child.status);
+        return child.report;
+    }
+    let config = &RunConfig {
+        timeout: options.timeout,
+        variant: options.variant.clone(),
+        check: options.check,
+    };
+    let on_part = &mut |part: &PartReport| {
+        if !options.json {
+            print_part(part)
+        }
+    };
+    let day_report = match aoc_202x::run_day(day, input_fp, config, on_part) {
+        Ok(day_report) => day_report,
+        Err(e) => {
+            println!("Could not run day {}: {}", day, e);
+            return None;
+        }
+    };
+    if !options.no_history {
+        history::record(day, options.example, &day_report);
+    }
+    if options.json {
+        report::print_json(day, options.example, Some(&day_report), answers, None);
+    } else {
+        print_day_end(&day_report, options.check);
+    }
+    Some(day_report)
+}
+
+fn print_day_end(day_report: &DayReport, check: bool) {
+    match (&day_report.parse, &day_report.prepare) {
+        (Err(e), _) => println!("Unable to parse file: {}", e),
+        (Ok(_), None) if check => match day_report.violations.is_empty() {
+            true => println!("The input is valid"),
+            false => day_report
+                .violations
+                .iter()
+                .for_each(|violation| println!("The input breaks an assumption: {}", violation)),
+        },
+        (Ok(_), Some(Err(e))) => println!("Unable to prepare input: {}", e),
+        (Ok(_), _) => report::print_parse(day_report),
+    }
+}
+
+fn download_all_input(puzzle: bool) {
+    (1..=25).for_each(|day| download_input(day, puzzle, false))
+}
+
+// How long to wait between attempts when the input isn't there right at unlock,
+// e.g. because our clock runs a little ahead of the site's
+const UNLOCK_BACKOFF_MS: [u64; 5] = [500, 1000, 2000, 4000, 8000];
+
+// With `retry`, keeps asking for a few seconds before giving up on the input
+fn download_input(day: usize, puzzle: bool, retry: bool) {
+    let path = format!("{}/day/{}/input", YEAR, day);
+    let mut response = client::get(&path);
+    let mut backoff = UNLOCK_BACKOFF_MS.iter().filter(|_| retry);
+    while !response.status().is_success() {
+        let Some(ms) = backoff.next() else {
+            break;
+        };
+        println!(
+            "Input for day {} isn't available yet ({}), retrying in {}ms",
+            day,
+            response.status(),
+            ms
+        );
+        thread::sleep(std::time::Duration::from_millis(*ms));
+        response = client::get(&path);
+    }
 
     if response.status().is_success() {
         let mut text = response.text().unwrap();
         // Remove trailing newline
         text.pop();
-        let path = format!("inputs/day{:02}.txt", day);
-        fs::write(&path, text).unwrap();
-        println!("Successfully downloaded input to {}", &path);
+        let path = input_path(day);
+        replace_file(&path, &text);
+        println!("Successfully downloaded input to {}", path);
     } else {
         panic!(
             "Could not get input for day {}. Is your correct session cookie in your .session file?",
             day
         )
     }
+
+    if puzzle {
+        download_puzzle(day);
+    }
+}
+
+fn download_puzzle(day: usize) {
+    let response = client::get(&format!("{}/day/{}", YEAR, day));
+    if response.status().is_success() {
+        let path = puzzle_path(day);
+        fs::write(&path, client::puzzle_text(&response.text().unwrap())).unwrap();
+        println!("Successfully downloaded puzzle text to {}", path);
+    } else {
+        panic!("Could not get the puzzle text for day {}", day)
+    }
 }
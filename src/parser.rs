@@ -1,9 +1,37 @@
+use std::{fmt::Display, time::Duration};
+
 use nom::{error::Error, Err};
 
 #[derive(Debug)]
 pub enum MyErr {
     FileError(std::io::Error),
     ParseError(Err<Error<String>>),
+    SolveError(String),
+    Panic(String),
+    Unimplemented(String),
+    Timeout(Duration),
+    UnknownYear(usize),
+    UnknownDay(usize),
+    UnknownPart(usize),
+    UnknownVariant(String),
+    Utf8Error(std::str::Utf8Error),
+}
+
+impl Display for MyErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MyErr::FileError(e) => write!(f, "could not read input: {}", e),
+            MyErr::ParseError(e) => write!(f, "could not parse input: {}", e),
+            MyErr::SolveError(e) => write!(f, "{}", e),
+            MyErr::Panic(e) | MyErr::Unimplemented(e) => write!(f, "panicked: {}", e),
+            MyErr::Timeout(d) => write!(f, "timed out after {}", humantime::format_duration(*d)),
+            MyErr::UnknownYear(year) => write!(f, "there are no solutions for {}", year),
+            MyErr::UnknownDay(day) => write!(f, "there is no day {}", day),
+            MyErr::UnknownPart(part) => write!(f, "there is no part {}", part),
+            MyErr::UnknownVariant(name) => write!(f, "there is no variant called {}", name),
+            MyErr::Utf8Error(e) => write!(f, "input is not UTF-8: {}", e),
+        }
+    }
 }
 
 impl From<Err<Error<&str>>> for MyErr {
@@ -17,6 +45,24 @@ impl From<Err<Error<&str>>> for MyErr {
     }
 }
 
+// For days that parse bytes
+impl From<Err<Error<&[u8]>>> for MyErr {
+    fn from(e: Err<Error<&[u8]>>) -> MyErr {
+        let inner_err = match e {
+            Err::Incomplete(n) => Err::Incomplete(n),
+            Err::Error(e) => Err::Error(conv_byte_error(e)),
+            Err::Failure(e) => Err::Failure(conv_byte_error(e)),
+        };
+        MyErr::ParseError(inner_err)
+    }
+}
+
+impl From<std::str::Utf8Error> for MyErr {
+    fn from(e: std::str::Utf8Error) -> MyErr {
+        MyErr::Utf8Error(e)
+    }
+}
+
 impl From<std::io::Error> for MyErr {
     fn from(e: std::io::Error) -> MyErr {
         MyErr::FileError(e)
@@ -29,3 +75,10 @@ fn conv_error(e: Error<&str>) -> Error<String> {
         code: e.code,
     }
 }
+
+fn conv_byte_error(e: Error<&[u8]>) -> Error<String> {
+    Error {
+        input: String::from_utf8_lossy(e.input).into_owned(),
+        code: e.code,
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    io::{stderr, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use log::info;
+
+static SILENT: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProgressBar>> = const { RefCell::new(None) };
+}
+
+/// Progress of the part that is currently running. Parts that take a while can
+/// set a total and increment towards it; the runner draws a bar with an ETA on a
+/// terminal. Cloning is cheap, and updates are cheap enough to batch every few
+/// thousand iterations.
+#[derive(Clone)]
+pub struct Progress(ProgressBar);
+
+// The progress of the part running on this thread. Days that spawn their own
+// threads should clone it before doing so.
+pub fn progress() -> Progress {
+    CURRENT.with_borrow(|bar| match bar {
+        Some(bar) => Progress(bar.clone()),
+        None => Progress(ProgressBar::hidden()),
+    })
+}
+
+impl Progress {
+    pub fn set_total(&self, total: u64) {
+        self.0.set_length(total)
+    }
+
+    pub fn inc(&self, delta: u64) {
+        self.0.inc(delta)
+    }
+
+    pub fn set_message(&self, message: impl Into<Cow<'static, str>>) {
+        self.0.set_message(message)
+    }
+}
+
+// Stops progress from being drawn or summarised at all, e.g. for JSON output
+pub fn silence() {
+    SILENT.store(true, Ordering::Relaxed)
+}
+
+pub(crate) fn start(part: usize) -> ProgressBar {
+    let bar = match SILENT.load(Ordering::Relaxed) {
+        true => ProgressBar::hidden(),
+        // Draws nothing when stderr is not a terminal
+        false => ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr()),
+    };
+    bar.set_style(
+        ProgressStyle::with_template(
+            "{spinner} Part {prefix} [{elapsed_precise}] [{wide_bar}] {human_pos}/{human_len} {msg} ETA {eta}",
+        )
+        .unwrap(),
+    );
+    bar.set_prefix(part.to_string());
+    bar.enable_steady_tick(Duration::from_millis(100));
+    bar
+}
+
+// Makes `bar` the progress that `progress()` returns on this thread
+pub(crate) fn set_current(bar: ProgressBar) {
+    CURRENT.set(Some(bar))
+}
+
+pub(crate) fn finish(bar: ProgressBar) {
+    bar.finish_and_clear();
+    // Without a bar to watch, leave a one-line summary of parts that reported progress
+    let interactive = stderr().is_terminal();
+    if !interactive && !SILENT.load(Ordering::Relaxed) && bar.length().is_some() {
+        info!(
+            "part {} progress: {}/{} {}",
+            bar.prefix(),
+            bar.position(),
+            bar.length().unwrap_or_default(),
+            bar.message()
+        );
+    }
+}
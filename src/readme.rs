@@ -0,0 +1,95 @@
+use std::{fmt::Write, fs, time::Duration};
+
+use crate::answers::{answers_path, Answers};
+use crate::history;
+
+const README_PATH: &str = "README.md";
+const START: &str = "<!-- progress:start -->";
+const END: &str = "<!-- progress:end -->";
+
+// Rewrites the progress section of the README from the recorded answers and timings
+pub fn update(year: usize) {
+    let readme = fs::read_to_string(README_PATH).expect("Could not read README.md");
+    let readme = replace_section(&readme, &table(year));
+    fs::write(README_PATH, readme).expect("Could not write README.md");
+    println!("Updated the progress table in {}", README_PATH);
+}
+
+// A part earns its star once its answer is recorded with `answer`. Timings are
+// each part's best run on the real input from the history.
+fn table(year: usize) -> String {
+    let mut table =
+        String::from("| Day | Stars | Part 1 | Part 2 |\n| ---: | :---: | ---: | ---: |\n");
+    let mut stars = 0;
+    for day in 1..=25 {
+        let answers = Answers::load(&answers_path(day, false));
+        let history = history::load(day, false);
+        let best = history::best(&history);
+        let solved: Vec<bool> = (1..=2).map(|part| answers.get(part).is_some()).collect();
+        if !solved.contains(&true) && best.iter().all(Option::is_none) {
+            continue;
+        }
+        stars += solved.iter().filter(|solved| **solved).count();
+        let timing = |part: usize| match best[part - 1] {
+            Some(entry) => format_elapsed(entry.elapsed),
+            None => String::from("-"),
+        };
+        writeln!(
+            table,
+            "| [{}](https://adventofcode.com/{}/day/{}) | {} | {} | {} |",
+            day,
+            year,
+            day,
+            solved
+                .iter()
+                .map(|solved| if *solved { "⭐" } else { "☆" })
+                .collect::<String>(),
+            timing(1),
+            timing(2)
+        )
+        .unwrap();
+    }
+    table + &format!("\nStars collected: {}/50\n", stars)
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    match elapsed.as_secs_f64() {
+        secs if secs >= 1.0 => format!("{:.2}s", secs),
+        secs if secs >= 1e-3 => format!("{:.2}ms", secs * 1e3),
+        secs => format!("{:.0}µs", secs * 1e6),
+    }
+}
+
+// Replaces whatever is between the markers, or appends a new section if there are none
+fn replace_section(readme: &str, table: &str) -> String {
+    let section = format!("{}\n{}{}", START, table, END);
+    match (readme.find(START), readme.find(END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!(
+                "{}{}{}",
+                &readme[..start],
+                section,
+                &readme[end + END.len()..]
+            )
+        }
+        _ => format!("{}\n## Progress\n\n{}\n", readme.trim_end(), section),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::replace_section;
+
+    #[test]
+    fn test_replace_section() {
+        let readme = "# AoC\n<!-- progress:start -->\nold\n<!-- progress:end -->\nmore\n";
+        assert_eq!(
+            replace_section(readme, "new\n"),
+            "# AoC\n<!-- progress:start -->\nnew\n<!-- progress:end -->\nmore\n"
+        );
+        assert_eq!(
+            replace_section("# AoC\n", "new\n"),
+            "# AoC\n## Progress\n\n<!-- progress:start -->\nnew\n<!-- progress:end -->\n"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use aoc_202x::alloc::AllocStats;
+use aoc_202x::{DayReport, FailureKind, PartReport};
+use indicatif::HumanBytes;
+use log::info;
+use serde::Serialize;
+
+use crate::answers::{Answers, Check};
+use crate::isolate::ChildStatus;
+
+fn print_alloc(phase: &str, alloc: &AllocStats) {
+    println!(
+        "{} made {} allocations, {} in total, {} at peak",
+        phase,
+        alloc.allocations,
+        HumanBytes(alloc.bytes),
+        HumanBytes(alloc.peak)
+    );
+}
+
+// Parsing and preparing, after the parts. Only has something to say when built
+// with the alloc-stats feature; timings are logged, as most days don't prepare.
+pub fn print_parse(report: &DayReport) {
+    if let Some(alloc) = &report.parse_alloc {
+        print_alloc("Parsing", alloc);
+    }
+    if let Ok(elapsed) = report.parse {
+        info!("parsing took {}ms", elapsed.as_nanos() as f32 / 1e6);
+    }
+    if let Some(Ok(elapsed)) = report.prepare {
+        info!("preparing took {}ms", elapsed.as_nanos() as f32 / 1e6);
+    }
+    if let Some(alloc) = &report.prepare_alloc {
+        print_alloc("Preparing", alloc);
+    }
+}
+
+pub fn print_part(part: &PartReport, answers: &Answers) {
+    if let Some(variant) = &part.variant {
+        println!("Part {} uses the {} variant", part.part, variant);
+    }
+    match &part.answer {
+        Ok(answer) => println!(
+            "Part {}: {}{}",
+            part.part,
+            answer,
+            answers.check(part.part, answer)
+        ),
+        Err(e) => println!("Part {} failed: {}", part.part, e),
+    }
+    println!(
+        "Part {} took {}ms",
+        part.part,
+        part.elapsed.as_nanos() as f32 / 1e6
+    );
+    if let Some(alloc) = &part.alloc {
+        print_alloc(&format!("Part {}", part.part), alloc);
+    }
+}
+
+pub fn print_child(status: &ChildStatus) {
+    if let Some(crash) = &status.crash {
+        println!("Crashed: {}", crash);
+    }
+    if let Some(peak_rss) = status.peak_rss {
+        println!("Peak RSS was {}", HumanBytes(peak_rss));
+    }
+}
+
+// Whether `run --check` fails for a day: its input didn't parse or broke an
+// assumption, or the isolated child crashed. With `--all`, days without an input
+// or a parser yet are skipped, as there is nothing to check.
+pub fn check_failed(report: Option<&DayReport>, all: bool) -> bool {
+    match report {
+        Some(DayReport { parse: Err(e), .. }) => {
+            !(all && matches!(e.kind, FailureKind::NoInput | FailureKind::Unimplemented))
+        }
+        Some(report) => !report.violations.is_empty(),
+        None => true,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Correct,
+    Wrong,
+    Unverified,
+    Unimplemented,
+    Failed,
+}
+
+impl Status {
+    const ALL: [Status; 5] = [
+        Status::Correct,
+        Status::Wrong,
+        Status::Unverified,
+        Status::Unimplemented,
+        Status::Failed,
+    ];
+
+    fn of(part: &PartReport, answers: &Answers) -> Status {
+        match &part.answer {
+            Ok(answer) => match answers.check(part.part, answer) {
+                Check::Correct => Status::Correct,
+                Check::Wrong { .. } => Status::Wrong,
+                Check::Unverified => Status::Unverified,
+            },
+            Err(e) if e.kind == FailureKind::Unimplemented => Status::Unimplemented,
+            Err(_) => Status::Failed,
+        }
+    }
+
+    fn index(self) -> usize {
+        Status::ALL.iter().position(|s| *s == self).unwrap()
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unverified => "unverified",
+            Status::Unimplemented => "unimplemented",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+// How many of the slowest parts get a star in the summary
+const SLOWEST: usize = 3;
+const ANSWER_WIDTH: usize = 16;
+
+fn cell(answer: &str) -> String {
+    match answer.chars().count() > ANSWER_WIDTH {
+        true => answer.chars().take(ANSWER_WIDTH - 1).collect::<String>() + "…",
+        false => answer.to_string(),
+    }
+}
+
+// The table printed after `run --all`: one row per day, the slowest parts starred
+pub fn print_summary(days: &[(usize, Option<DayReport>, Answers)]) {
+    let mut slowest: Vec<(usize, usize, Duration)> = days
+        .iter()
+        .flat_map(|(day, report, _)| {
+            report
+                .iter()
+                .flat_map(move |r| &r.parts)
+                .map(move |p| (*day, p))
+        })
+        .filter(|(_, part)| part.answer.is_ok())
+        .map(|(day, part)| (day, part.part, part.elapsed))
+        .collect();
+    slowest.sort_by_key(|(_, _, elapsed)| std::cmp::Reverse(*elapsed));
+    slowest.truncate(SLOWEST);
+
+    let mut counts = [0; Status::ALL.len()];
+    let mut total = Duration::ZERO;
+    println!("======== SUMMARY ========");
+    println!("Day  {:<46}Part 2", "Part 1");
+    for (day, report, answers) in days {
+        print!("{:>3}", day);
+        let parts = match report {
+            Some(DayReport {
+                parse: Ok(_),
+                prepare: Some(Err(_)),
+                ..
+            }) => {
+                println!("  unable to prepare input");
+                continue;
+            }
+            Some(DayReport {
+                parse: Ok(_),
+                parts,
+                ..
+            }) => parts,
+            Some(DayReport { parse: Err(e), .. }) if e.kind == FailureKind::NoInput => {
+                println!("  no input");
+                continue;
+            }
+            // A scaffolded day's parser is still `unimplemented!()`, so neither part is
+            Some(DayReport { parse: Err(e), .. }) if e.kind == FailureKind::Unimplemented => {
+                println!("  {}", Status::Unimplemented.name());
+                counts[Status::Unimplemented.index()] += 2;
+                continue;
+            }
+            Some(_) => {
+                println!("  unable to parse input");
+                continue;
+            }
+            // Only with --isolate, when the child crashed
+            None => {
+                println!("  crashed");
+                continue;
+            }
+        };
+        for part in parts {
+            let status = Status::of(part, answers);
+            counts[status.index()] += 1;
+            total += part.elapsed;
+            let star = match slowest.contains(&(*day, part.part, part.elapsed)) {
+                true => "*",
+                false => " ",
+            };
+            print!(
+                "  {:<13} {:<16} {:>10.3}ms{}",
+                status.name(),
+                cell(part.answer.as_deref().unwrap_or("")),
+                part.elapsed.as_secs_f64() * 1e3,
+                star
+            );
+        }
+        println!();
+    }
+    let parts: usize = counts.iter().sum();
+    let counts: Vec<String> = Status::ALL
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(status, count)| format!("{} {}", count, status.name()))
+        .collect();
+    println!(
+        "Total: {:.3}ms over {} parts ({})",
+        total.as_secs_f64() * 1e3,
+        parts,
+        counts.join(", ")
+    );
+    if !slowest.is_empty() {
+        println!("* the {} slowest parts", slowest.len());
+    }
+}
+
+#[derive(Serialize)]
+struct JsonDay<'a> {
+    day: usize,
+    example: bool,
+    #[serde(flatten)]
+    report: Option<&'a DayReport>,
+    // Per part: whether the answer matched the recorded one, null if unknown
+    correct: Vec<Option<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    child: Option<&'a ChildStatus>,
+}
+
+// One line of JSON per day. Without a report (the isolated child crashed) only
+// the child's status is included.
+pub fn print_json(
+    day: usize,
+    example: bool,
+    report: Option<&DayReport>,
+    answers: &Answers,
+    child: Option<&ChildStatus>,
+) {
+    let correct = report
+        .iter()
+        .flat_map(|report| &report.parts)
+        .map(|part| match &part.answer {
+            Ok(answer) => match answers.check(part.part, answer) {
+                Check::Correct => Some(true),
+                Check::Wrong { .. } => Some(false),
+                Check::Unverified => None,
+            },
+            Err(_) => None,
+        })
+        .collect();
+    let line = JsonDay {
+        day,
+        example,
+        report,
+        correct,
+        child,
+    };
+    println!("{}", serde_json::to_string(&line).unwrap());
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use aoc_202x::{DayReport, Failure, FailureKind};
+
+    use super::check_failed;
+
+    fn report(parse: Result<(), FailureKind>, violations: &[&str]) -> DayReport {
+        DayReport {
+            parse: parse.map(|_| Duration::ZERO).map_err(|kind| Failure {
+                kind,
+                message: String::from("could not parse input"),
+            }),
+            parse_alloc: None,
+            prepare: None,
+            prepare_alloc: None,
+            violations: violations.iter().map(|v| v.to_string()).collect(),
+            parts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_failed() {
+        assert!(!check_failed(Some(&report(Ok(()), &[])), false));
+        let invalid = report(Ok(()), &["there are 3 times but 2 distances"]);
+        assert!(check_failed(Some(&invalid), true));
+        let unparsable = report(Err(FailureKind::Failed), &[]);
+        assert!(check_failed(Some(&unparsable), false));
+        assert!(check_failed(Some(&unparsable), true));
+        let missing = report(Err(FailureKind::NoInput), &[]);
+        assert!(check_failed(Some(&missing), false));
+        assert!(!check_failed(Some(&missing), true));
+        let stub = report(Err(FailureKind::Unimplemented), &[]);
+        assert!(!check_failed(Some(&stub), true));
+        assert!(check_failed(None, true));
+    }
+}
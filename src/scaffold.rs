@@ -0,0 +1,104 @@
+use std::{fs, path::Path};
+
+use crate::example_path;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const DAYS_MOD: &str = "src/days/mod.rs";
+const DISPATCHER: &str = "src/lib.rs";
+
+fn day_path(day: usize) -> String {
+    format!("src/days/day{:02}.rs", day)
+}
+
+// A day counts as implemented once its part_1 stub has been replaced
+fn is_implemented(path: &str) -> bool {
+    match fs::read_to_string(path) {
+        Ok(source) => !source.contains("unimplemented!(\"part_1\")"),
+        Err(_) => false,
+    }
+}
+
+pub fn new_day(day: usize) {
+    let path = day_path(day);
+    if is_implemented(&path) {
+        panic!(
+            "Day {} is already implemented in {}, refusing to overwrite it",
+            day, path
+        );
+    }
+    fs::write(&path, TEMPLATE.replace("{{DAY}}", &format!("{:02}", day))).unwrap();
+    println!("Wrote day {} template to {}", day, path);
+
+    let example = example_path(day);
+    if !Path::new(&example).exists() {
+        fs::write(&example, "").unwrap();
+        println!("Created empty example file {}", example);
+    }
+
+    register(DAYS_MOD, day, &format!("pub mod day{:02};", day), |line| {
+        line.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    });
+    register(
+        DISPATCHER,
+        day,
+        &format!("({}, &day{:02}::Day{:02}),", day, day, day),
+        |line| line.strip_prefix('(')?.split_once(", &day")?.0.parse().ok(),
+    );
+}
+
+pub fn new_year(year: usize) {
+    let implemented: Vec<usize> = (1..=25)
+        .filter(|day| is_implemented(&day_path(*day)))
+        .collect();
+    if !implemented.is_empty() {
+        panic!(
+            "Days {:?} are already implemented, move them out of src/days before starting {}",
+            implemented, year
+        );
+    }
+
+    let lib = fs::read_to_string(DISPATCHER).unwrap();
+    let lib: Vec<String> = lib
+        .lines()
+        .map(|line| match line.starts_with("pub const YEAR: usize = ") {
+            true => format!("pub const YEAR: usize = {};", year),
+            false => line.to_string(),
+        })
+        .collect();
+    fs::write(DISPATCHER, lib.join("\n") + "\n").unwrap();
+    println!("Set YEAR to {} in {}", year, DISPATCHER);
+
+    (1..=25).for_each(new_day)
+}
+
+// Inserts `entry` into the sorted run of lines recognised by `day_of`,
+// unless that run already has a line for `day`.
+fn register(path: &str, day: usize, entry: &str, day_of: impl Fn(&str) -> Option<usize>) {
+    let source = fs::read_to_string(path).unwrap();
+    let mut lines: Vec<String> = source.lines().map(|l| l.to_string()).collect();
+    let entries: Vec<(usize, usize)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_of(line.trim()).map(|d| (index, d)))
+        .collect();
+    if entries.iter().any(|&(_, d)| d == day) {
+        return;
+    }
+    let Some(&(last, _)) = entries.last() else {
+        panic!("Could not find where to register day {} in {}", day, path);
+    };
+    let at = entries
+        .iter()
+        .find(|&&(_, d)| d > day)
+        .map_or(last + 1, |&(index, _)| index);
+    let indent: String = lines[last]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    lines.insert(at, format!("{}{}", indent, entry));
+    fs::write(path, lines.join("\n") + "\n").unwrap();
+    println!("Registered day {} in {}", day, path);
+}
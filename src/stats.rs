@@ -0,0 +1,153 @@
+use std::{fmt::Write, fs, time::Duration};
+
+use crate::client;
+use crate::timer::{self, Timer};
+
+#[derive(Debug, PartialEq)]
+struct PartStats {
+    // As the site shows it: hh:mm:ss after the unlock, or >24h
+    time: String,
+    rank: u64,
+    score: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct DayStats {
+    day: usize,
+    parts: [Option<PartStats>; 2],
+}
+
+// Shows the personal times and ranks, from `file` (a saved copy of the page) if given
+pub fn show(year: usize, file: Option<&str>) {
+    let html = match file {
+        Some(file) => fs::read_to_string(file).expect("Could not read the saved stats page"),
+        None => {
+            let response = client::get(&format!("{}/leaderboard/self", year));
+            if !response.status().is_success() {
+                panic!("Could not get your stats. Is your correct session cookie in your .session file?")
+            }
+            response.text().unwrap()
+        }
+    };
+    let days = parse(&html);
+    if days.is_empty() {
+        panic!("No stats found. Have you collected any stars in {}?", year)
+    }
+    print!("{}", render(&days, Timer::load));
+}
+
+// Rows of the table in the page's <pre>, oldest day first
+fn parse(html: &str) -> Vec<DayStats> {
+    let mut days: Vec<DayStats> = client::strip_tags(html)
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            let [day, time_1, rank_1, score_1, time_2, rank_2, score_2] = columns[..] else {
+                return None;
+            };
+            let part = |time: &str, rank: &str, score: &str| {
+                Some(PartStats {
+                    time: time.to_string(),
+                    rank: rank.parse().ok()?,
+                    score: score.parse().ok()?,
+                })
+            };
+            Some(DayStats {
+                day: day.parse().ok()?,
+                parts: [part(time_1, rank_1, score_1), part(time_2, rank_2, score_2)],
+            })
+        })
+        .collect();
+    days.sort_by_key(|day| day.day);
+    days
+}
+
+fn parse_time(time: &str) -> Option<Duration> {
+    let mut secs = 0;
+    for unit in time.split(':') {
+        secs = secs * 60 + unit.parse::<u64>().ok()?;
+    }
+    Some(Duration::from_secs(secs))
+}
+
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+// Next to the site's times and ranks: how long each part took us after we
+// started the timer, and how long part 2 took after part 1
+fn render(days: &[DayStats], timer: impl Fn(usize) -> Timer) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "Day  {:>8} {:>7} {:>8}  {:>8} {:>7} {:>8}  {:>8}",
+        "Part 1", "Rank", "Ours", "Part 2", "Rank", "Ours", "Gap"
+    )
+    .unwrap();
+    for day in days {
+        let timer = timer(day.day);
+        let column = |part: &Option<PartStats>| match part {
+            Some(part) => (part.time.clone(), part.rank.to_string()),
+            None => (String::from("-"), String::from("-")),
+        };
+        let ((time_1, rank_1), (time_2, rank_2)) = (column(&day.parts[0]), column(&day.parts[1]));
+        let gap = match (parse_time(&time_1), parse_time(&time_2)) {
+            (Some(time_1), Some(time_2)) => format_time(time_2.saturating_sub(time_1)),
+            _ => String::from("-"),
+        };
+        writeln!(
+            out,
+            "{:>3}  {:>8} {:>7} {:>8}  {:>8} {:>7} {:>8}  {:>8}",
+            day.day,
+            time_1,
+            rank_1,
+            timer::format_gap(timer.started, timer.solved[0]),
+            time_2,
+            rank_2,
+            timer::format_gap(timer.started, timer.solved[1]),
+            gap
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::{parse, render, PartStats};
+    use crate::timer::Timer;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard_self.html");
+
+    #[test]
+    fn test_parse() {
+        let days = parse(FIXTURE);
+        let numbers: Vec<usize> = days.iter().map(|day| day.day).collect();
+        assert_eq!(numbers, [1, 4, 5, 6]);
+        assert_eq!(
+            days[2].parts[1],
+            Some(PartStats {
+                time: String::from(">24h"),
+                rank: 55832,
+                score: 0
+            })
+        );
+        assert_eq!(days[1].parts[1], None);
+    }
+
+    #[test]
+    fn test_render() {
+        let days = parse(FIXTURE);
+        let at = |h, m, s| Some(Utc.with_ymd_and_hms(2023, 12, 4, h, m, s).unwrap());
+        let table = render(&days[1..2], |_| Timer {
+            started: at(5, 3, 0),
+            solved: [at(5, 20, 15), None],
+        });
+        let row = table.lines().nth(1).unwrap();
+        assert!(row.starts_with("  4 "), "{}", row);
+        assert!(row.contains(" 00:17:15 "), "{}", row);
+    }
+}
@@ -0,0 +1,129 @@
+use std::fs;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::calendar;
+
+/// When we started a puzzle, and when each part's answer was accepted
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Timer {
+    pub started: Option<DateTime<Utc>>,
+    pub solved: [Option<DateTime<Utc>>; 2],
+}
+
+fn timer_path(day: usize) -> String {
+    format!("inputs/day{:02}.timer.json", day)
+}
+
+impl Timer {
+    pub fn load(day: usize) -> Timer {
+        fs::read_to_string(timer_path(day))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, day: usize) {
+        fs::write(timer_path(day), serde_json::to_string(self).unwrap() + "\n").unwrap();
+    }
+}
+
+// Starts the timer for `day`, unless it is already running
+pub fn start(day: usize) {
+    let mut timer = Timer::load(day);
+    match timer.started {
+        Some(started) => println!(
+            "The timer for day {} has been running since {}",
+            day,
+            started.format("%Y-%m-%d %H:%M:%S UTC")
+        ),
+        None => {
+            timer.started = Some(Utc::now());
+            timer.save(day);
+            println!("Started the timer for day {}", day);
+        }
+    }
+}
+
+// Records that `part` was just solved, unless it already was
+pub fn solved(day: usize, part: usize) {
+    let mut timer = Timer::load(day);
+    if timer.solved[part - 1].is_none() {
+        timer.solved[part - 1] = Some(Utc::now());
+        timer.save(day);
+    }
+}
+
+pub fn format_gap(from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> String {
+    match (from, to) {
+        (Some(from), Some(to)) => {
+            let secs = (to - from).num_seconds().max(0);
+            format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        }
+        _ => String::from("-"),
+    }
+}
+
+// Time to each star since the unlock and since we started, and the gap between them
+pub fn report(year: usize, day: usize, timer: &Timer) -> String {
+    let unlock = Some(calendar::unlock_time(year, day));
+    let [part_1, part_2] = timer.solved;
+    format!(
+        "{:>3}  {:>8} {:>8}  {:>8} {:>8}  {:>8}",
+        day,
+        format_gap(unlock, part_1),
+        format_gap(timer.started, part_1),
+        format_gap(unlock, part_2),
+        format_gap(timer.started, part_2),
+        format_gap(part_1, part_2)
+    )
+}
+
+// Every day with a timer, or only `day`
+pub fn show(year: usize, day: Option<usize>) {
+    let days: Vec<usize> = match day {
+        Some(day) => vec![day],
+        None => (1..=25).collect(),
+    };
+    println!("Day  {:>17}  {:>17}  {:>8}", "Part 1", "Part 2", "Gap");
+    println!(
+        "     {:>8} {:>8}  {:>8} {:>8}",
+        "unlock", "start", "unlock", "start"
+    );
+    for day in days {
+        let timer = Timer::load(day);
+        if timer.started.is_some() || timer.solved.iter().any(Option::is_some) {
+            println!("{}", report(year, day, &timer));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::{report, Timer};
+
+    #[test]
+    fn test_report() {
+        // Day 1 of 2023 unlocked at 05:00 UTC
+        let at = |h, m, s| Some(Utc.with_ymd_and_hms(2023, 12, 1, h, m, s).unwrap());
+        let timer = Timer {
+            started: at(5, 10, 0),
+            solved: [at(5, 25, 30), None],
+        };
+        assert_eq!(
+            report(2023, 1, &timer),
+            "  1  00:25:30 00:15:30         -        -         -"
+        );
+        let timer = Timer {
+            solved: [at(5, 25, 30), at(6, 0, 0)],
+            ..timer
+        };
+        assert_eq!(
+            report(2023, 1, &timer),
+            "  1  00:25:30 00:15:30  01:00:00 00:50:00  00:34:30"
+        );
+    }
+}
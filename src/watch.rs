@@ -0,0 +1,75 @@
+use std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{answers::answers_path, example_path, input_path};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Re-runs `day` through `cargo run` whenever its source, inputs or answers change,
+// so edits to the solution are recompiled. Never touches the network.
+pub fn watch(day: usize) {
+    let files = [
+        format!("src/days/day{:02}.rs", day),
+        example_path(day),
+        answers_path(day, true),
+        input_path(day),
+        answers_path(day, false),
+    ];
+    let mut last_seen: Option<Vec<Option<SystemTime>>> = None;
+    loop {
+        let modified: Vec<Option<SystemTime>> = files
+            .iter()
+            .map(|f| fs::metadata(f).and_then(|m| m.modified()).ok())
+            .collect();
+        if last_seen.as_ref() != Some(&modified) {
+            last_seen = Some(modified);
+            // Clear the screen and move the cursor to the top
+            print!("\x1B[2J\x1B[H");
+            println!("Watching day {} for changes, press Ctrl-C to stop", day);
+            if has_example(day) {
+                rerun(day, true);
+            }
+            rerun(day, false);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn has_example(day: usize) -> bool {
+    fs::metadata(example_path(day)).is_ok_and(|m| m.len() > 0)
+}
+
+fn rerun(day: usize, example: bool) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    // `cargo run` hands us its package variables, and build scripts watching
+    // them would see a changed environment and rebuild everything
+    for (key, _) in env::vars() {
+        if key.starts_with("CARGO_PKG_") || key.starts_with("CARGO_MANIFEST_") {
+            command.env_remove(key);
+        }
+    }
+    // Never fetch crates or update the index, even if Cargo.lock changed
+    command.args(["run", "--quiet", "--offline"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command.args(["--", "run", &day.to_string()]);
+    if example {
+        println!("-------- example --------");
+        command.arg("--example");
+    } else if Path::new(&input_path(day)).exists() {
+        println!("--------- input ---------");
+    } else {
+        println!("No input for day {} yet", day);
+        return;
+    }
+    if let Err(e) = command.status() {
+        println!("Could not run cargo: {}", e);
+    }
+}
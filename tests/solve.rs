@@ -0,0 +1,276 @@
+use aoc_202x::days::{day01::Day01, day04::Day04, parse_utf8};
+use aoc_202x::{
+    find_day, run_day, solve, Answer, Day, DynDay, Error, FailureKind, RunConfig, Shown, DAYS,
+    DEFAULT_VARIANT, GENERATORS, YEAR,
+};
+use nom::{
+    character::complete::{line_ending, u32},
+    multi::separated_list1,
+};
+
+const DAY04_EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+#[test]
+fn test_solve_example() {
+    assert_eq!(solve(YEAR, 4, 1, DAY04_EXAMPLE).unwrap(), "13");
+    assert_eq!(solve(YEAR, 4, 2, DAY04_EXAMPLE).unwrap(), "30");
+}
+
+#[test]
+fn test_solve_errors() {
+    assert!(matches!(
+        solve(YEAR - 1, 4, 1, ""),
+        Err(Error::UnknownYear(_))
+    ));
+    assert!(matches!(solve(YEAR, 26, 1, ""), Err(Error::UnknownDay(26))));
+    assert!(matches!(
+        solve(YEAR, 4, 3, DAY04_EXAMPLE),
+        Err(Error::UnknownPart(3))
+    ));
+    assert!(matches!(
+        solve(YEAR, 4, 1, "not a card"),
+        Err(Error::ParseError(_))
+    ));
+}
+
+#[test]
+fn test_dyn_days() {
+    let numbers: Vec<usize> = DAYS.iter().map(|(number, _)| *number).collect();
+    assert_eq!(numbers, (1..=25).collect::<Vec<_>>());
+
+    let day = find_day(4).unwrap();
+    let input = day.parse_input(DAY04_EXAMPLE).unwrap();
+    let answers: Vec<String> = (1..=2)
+        .map(|part| day.solve_part(&input, part).answer.unwrap())
+        .collect();
+    assert_eq!(answers, ["13", "30"]);
+    assert!(day.parse_only(DAY04_EXAMPLE.as_bytes()).is_ok());
+    assert!(day.parse_only(b"not a card").is_err());
+}
+
+#[test]
+fn test_parse_str() {
+    // Day 4 counts each card's matches in `prepare`, which `parse_str` runs
+    let input = Day04::parse_str(DAY04_EXAMPLE).unwrap();
+    assert_eq!(Day04::part_1(&input), Ok(13));
+    assert_eq!(Day04::part_2(&input), Ok(30));
+    // and without which the parts fail rather than count no matches
+    let unprepared = Day04::parse_bytes(DAY04_EXAMPLE.as_bytes()).unwrap();
+    assert!(Day04::part_1(&unprepared).is_err());
+    assert!(Day04::part_2(&unprepared).is_err());
+    // Day 1 only has a byte parser, which `parse_str` goes through
+    let input = Day01::parse_str("1abc2\npqr3stu8vwx").unwrap();
+    assert_eq!(Day01::part_1(&input), 50);
+}
+
+// Sorts its input in place for part 2, which only works on an input of its own
+struct Median;
+
+impl Day for Median {
+    type Input<'a> = Vec<u32>;
+
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, Error> {
+        parse_utf8(input, separated_list1(line_ending, u32))
+    }
+
+    type Output1 = u32;
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
+        input.iter().sum()
+    }
+
+    type Output2 = u32;
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
+        Self::part_2_owned(input.clone())
+    }
+
+    fn part_2_owned(mut input: Self::Input<'_>) -> Self::Output2 {
+        input.sort_unstable();
+        input[input.len() / 2]
+    }
+}
+
+#[test]
+fn test_part_2_owned() {
+    let path = std::env::temp_dir().join("aoc_202x_median.txt");
+    std::fs::write(&path, "5\n1\n4\n2\n3").unwrap();
+    let report = Median::run_day(path.to_str().unwrap(), &RunConfig::default(), &mut |_| {});
+    let answers: Vec<_> = report
+        .parts
+        .iter()
+        .map(|part| part.answer.clone())
+        .collect();
+    assert_eq!(answers, [Ok("15".to_string()), Ok("3".to_string())]);
+
+    let input = Median.parse_input("5\n1\n4\n2\n3").unwrap();
+    assert_eq!(Median.solve_part_owned(input, 2).answer.unwrap(), "3");
+}
+
+// Part 1 is a stub, part 2 only panics with a message that reads like one
+struct Stub;
+
+impl Day for Stub {
+    type Input<'a> = Vec<u32>;
+
+    fn parse_bytes(input: &[u8]) -> Result<Self::Input<'_>, Error> {
+        parse_utf8(input, separated_list1(line_ending, u32))
+    }
+
+    type Output1 = u32;
+
+    fn part_1(_input: &Self::Input<'_>) -> Self::Output1 {
+        unimplemented!("part_1")
+    }
+
+    type Output2 = u32;
+
+    fn part_2(_input: &Self::Input<'_>) -> Self::Output2 {
+        panic!("not implemented yet")
+    }
+}
+
+#[test]
+fn test_failure_kinds() {
+    let config = &RunConfig::default();
+    let path = std::env::temp_dir().join("aoc_202x_stub.txt");
+    std::fs::write(&path, "1\n2").unwrap();
+    let report = Stub::run_day(path.to_str().unwrap(), config, &mut |_| {});
+    let kinds: Vec<_> = report
+        .parts
+        .iter()
+        .map(|part| part.answer.as_ref().unwrap_err().kind)
+        .collect();
+    assert_eq!(kinds, [FailureKind::Unimplemented, FailureKind::Failed]);
+
+    let missing = std::env::temp_dir().join("aoc_202x_missing.txt");
+    let report = Stub::run_day(missing.to_str().unwrap(), config, &mut |_| {});
+    assert_eq!(report.parse.unwrap_err().kind, FailureKind::NoInput);
+}
+
+#[test]
+fn test_solve_bytes() {
+    // Day 1 parses bytes instead of a string
+    let part_1 = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+    assert_eq!(solve(YEAR, 1, 1, part_1).unwrap(), "142");
+    let part_2 = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n\
+        4nineeightseven2\nzoneight234\n7pqrstsixteen";
+    assert_eq!(solve(YEAR, 1, 2, part_2).unwrap(), "281");
+}
+
+#[test]
+fn test_variants() {
+    let day = find_day(6).unwrap();
+    assert_eq!(day.variants(2), [DEFAULT_VARIANT, "quadratic"]);
+    let input = day
+        .parse_input("Time:      7  15   30\nDistance:  9  40  200")
+        .unwrap();
+    for variant in day.variants(1) {
+        assert_eq!(day.solve_variant(&input, 1, variant).answer.unwrap(), "288");
+    }
+    assert!(matches!(
+        day.solve_variant(&input, 1, "guesswork").answer,
+        Err(Error::UnknownVariant(_))
+    ));
+
+    let config = RunConfig {
+        variant: Some("guesswork".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        run_day(6, "inputs/day06.txt", &config, &mut |_| {}),
+        Err(Error::UnknownVariant(_))
+    ));
+
+    // Day 5's variant, which day 6 runs its default for, as when running all days
+    let path = std::env::temp_dir().join("aoc_202x_day06_intervals.txt");
+    std::fs::write(&path, "Time:      7  15   30\nDistance:  9  40  200").unwrap();
+    let config = RunConfig {
+        variant: Some("intervals".to_string()),
+        ..Default::default()
+    };
+    let report = run_day(6, path.to_str().unwrap(), &config, &mut |_| {}).unwrap();
+    let parts: Vec<_> = report
+        .parts
+        .iter()
+        .map(|part| (part.variant.clone(), part.answer.clone()))
+        .collect();
+    assert_eq!(
+        parts,
+        [
+            (None, Ok("288".to_string())),
+            (None, Ok("71503".to_string()))
+        ]
+    );
+}
+
+#[test]
+fn test_check() {
+    let path = std::env::temp_dir().join("aoc_202x_day06_uneven.txt");
+    std::fs::write(&path, "Time:      7  15   30\nDistance:  9  40").unwrap();
+    let config = RunConfig {
+        check: true,
+        ..Default::default()
+    };
+    let report = run_day(6, path.to_str().unwrap(), &config, &mut |_| {}).unwrap();
+    assert_eq!(report.violations, ["there are 3 times but 2 distances"]);
+    assert!(report.parts.is_empty());
+
+    let day = find_day(6).unwrap();
+    let input = day.parse_input("Time:      7\nDistance:  9").unwrap();
+    assert!(day.validate(&input).is_empty());
+}
+
+#[test]
+fn test_check_unparsable() {
+    let path = std::env::temp_dir().join("aoc_202x_day06_unparsable.txt");
+    std::fs::write(&path, "Time:      7  15   30\nDistances: 9  40  200").unwrap();
+    let config = RunConfig {
+        check: true,
+        ..Default::default()
+    };
+    let report = run_day(6, path.to_str().unwrap(), &config, &mut |_| {}).unwrap();
+    let failure = report.parse.unwrap_err();
+    assert_eq!(failure.kind, FailureKind::Failed);
+    assert!(failure.message.starts_with("could not parse input"));
+    assert!(report.violations.is_empty());
+    assert!(report.parts.is_empty());
+}
+
+#[test]
+fn test_generators() {
+    for &(number, generate) in GENERATORS {
+        let day = find_day(number).unwrap();
+        for seed in 0..20 {
+            let input = generate(&mut fastrand::Rng::with_seed(seed), 1 + seed as usize % 5);
+            let parsed = day.parse_input(&input).unwrap();
+            assert!(day.validate(&parsed).is_empty(), "{}", input);
+            for part in 1..=2 {
+                let answers: Vec<_> = day
+                    .variants(part)
+                    .into_iter()
+                    .map(|variant| day.solve_variant(&parsed, part, variant).answer.unwrap())
+                    .collect();
+                assert!(answers.iter().all(|a| *a == answers[0]), "{}", input);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_answers() {
+    struct Point(i32, i32);
+    impl std::fmt::Display for Point {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{},{}", self.0, self.1)
+        }
+    }
+    assert_eq!(Shown(Point(3, -4)).into_answer().unwrap(), "3,-4");
+    assert_eq!(true.into_answer().unwrap(), "true");
+    assert_eq!(Ok::<_, String>(2.5).into_answer().unwrap(), "2.5");
+}